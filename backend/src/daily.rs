@@ -0,0 +1,108 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ligames::{Tango, TangoGenerator, TangoSolver};
+use tokio::sync::Mutex;
+
+use crate::{blocking, error::ApiError};
+
+/// Size of the daily puzzle.
+const DAILY_SIZE: usize = 6;
+
+/// The puzzle of the day, the same for everyone on a UTC date. Only the
+/// latest one is kept, so it is generated once per day.
+#[derive(Clone, Default)]
+pub struct DailyPuzzle {
+    cached: Arc<Mutex<Option<(u32, Tango)>>>,
+}
+
+impl DailyPuzzle {
+    /// The puzzle for `date`, written as the number `yyyymmdd` which also
+    /// seeds the generator. Requests arriving while a new day's puzzle is
+    /// generated wait for it without blocking a runtime thread.
+    pub async fn get(
+        &self,
+        date: u32,
+        solver: Arc<dyn TangoSolver + Send + Sync>,
+    ) -> Result<Tango, ApiError> {
+        let mut cached = self.cached.lock().await;
+        if let Some((cached_date, tango)) = &*cached {
+            if *cached_date == date {
+                return Ok(tango.clone());
+            }
+        }
+        let tango = blocking(move || {
+            TangoGenerator::new(DAILY_SIZE, DAILY_SIZE)
+                .expect("The daily size is valid")
+                .with_solver(solver)
+                .with_seed(date.into())
+                .generate_unique()
+        })
+        .await?;
+        *cached = Some((date, tango.clone()));
+        Ok(tango)
+    }
+}
+
+/// Today's UTC date as `yyyymmdd`.
+pub fn today() -> u32 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System clock is before 1970")
+        .as_secs();
+    let (year, month, day) = civil_from_days(seconds / 86_400);
+    year * 10_000 + month * 100 + day
+}
+
+/// Converts days since 1970-01-01 to a Gregorian `(year, month, day)`.
+fn civil_from_days(days: u64) -> (u32, u32, u32) {
+    // Counts from 0000-03-01 in 400 year eras, putting leap days last
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year as u32, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use ligames::RecursiveTangoSolver;
+
+    use super::*;
+
+    fn solver() -> Arc<dyn TangoSolver + Send + Sync> {
+        Arc::new(RecursiveTangoSolver::default())
+    }
+
+    #[tokio::test]
+    async fn same_date_gives_the_same_puzzle() {
+        // Separate instances, so nothing comes from the cache
+        let first = DailyPuzzle::default().get(20261017, solver()).await;
+        let second = DailyPuzzle::default().get(20261017, solver()).await;
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_eq!(first, second);
+        assert_eq!(first.count_solutions(2), 1);
+    }
+
+    #[test]
+    fn dates_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(59), (1970, 3, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(20_743), (2026, 10, 17));
+    }
+}
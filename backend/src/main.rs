@@ -1,29 +1,728 @@
 use axum::{
-    extract::Json,
+    extract::{Json, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
-use ligames::TangoGenerator;
-use serde_json::json;
-use tower_http::cors::{Any, CorsLayer};
+use ligames::{
+    Coord, DifficultyTier, Game, Grid, MoveReplay, PlacementResult,
+    PuzzleAnalysis, Queens, RecursiveTangoSolver, RestrictionGraph,
+    SolveOutcome, Tango, TangoGenerator, TangoRestriction, TangoSolver,
+    TangoTile, TierThresholds, Zip,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tower_http::{
+    catch_panic::CatchPanicLayer,
+    cors::{Any, CorsLayer},
+    limit::RequestBodyLimitLayer,
+};
+
+mod daily;
+mod error;
+mod solve_cache;
+mod store;
+#[cfg(test)]
+mod tests;
+
+use daily::DailyPuzzle;
+use error::{ApiError, ApiJson, ApiQuery, ValidatedTango};
+use solve_cache::SolveCache;
+use store::PuzzleStore;
+use uuid::Uuid;
+
+#[derive(Clone)]
+struct AppState {
+    /// Solver used when generating puzzles.
+    solver: Arc<dyn TangoSolver + Send + Sync>,
+    /// Puzzles issued by `/api/tango-board`.
+    puzzles: PuzzleStore,
+    daily: DailyPuzzle,
+    /// Outcomes of recent `/api/solve` requests.
+    solved: SolveCache,
+    /// Scores splitting the `difficulty` tiers of `/api/tango-board`.
+    thresholds: TierThresholds,
+}
+
+/// How long issued puzzles can be fetched by id.
+const PUZZLE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Boards whose solve outcome is remembered.
+const SOLVE_CACHE_SIZE: usize = 256;
+
+/// Largest request body accepted by the `POST` endpoints, far above any
+/// board of at most `MAX_DIMENSION` cells a side.
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// Tiles the solver may try on a board sent by a client.
+const CLIENT_BOARD_STEP_LIMIT: usize = 1_000_000;
+
+/// Names a JSON file of [`TierThresholds`], as written by
+/// `ligames calibrate`. The default thresholds are used without it.
+const TIER_THRESHOLDS_VAR: &str = "LIGAMES_TIER_THRESHOLDS";
+
+fn load_tier_thresholds() -> TierThresholds {
+    let Some(path) = std::env::var_os(TIER_THRESHOLDS_VAR) else {
+        return TierThresholds::default();
+    };
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!("Failed to read {}: {}", path.to_string_lossy(), err)
+    });
+    serde_json::from_str(&json).unwrap_or_else(|err| {
+        panic!(
+            "Invalid tier thresholds in {}: {}",
+            path.to_string_lossy(),
+            err
+        )
+    })
+}
 
 #[tokio::main]
 async fn main() {
+    let puzzles = PuzzleStore::new(PUZZLE_TTL);
+    let sweeper = tokio::spawn({
+        let puzzles = puzzles.clone();
+        async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                puzzles.evict_expired();
+            }
+        }
+    });
+    let app = app(AppState {
+        solver: Arc::new(RecursiveTangoSolver::default()),
+        puzzles,
+        daily: DailyPuzzle::default(),
+        solved: SolveCache::new(SOLVE_CACHE_SIZE),
+        thresholds: load_tier_thresholds(),
+    });
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:8081").await.unwrap();
+    serve(listener, app, shutdown_signal()).await.unwrap();
+    sweeper.abort();
+}
+
+/// Serves `app` until `shutdown` resolves and the requests in flight are
+/// answered.
+async fn serve(
+    listener: tokio::net::TcpListener,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> std::io::Result<()> {
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+}
+
+/// Every endpoint of the server.
+fn app(state: AppState) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
-    let app = Router::new()
-        .route(
-            "/api/tango-board",
-            get(|| async {
-                axum::Json(serde_json::json!(
-                    TangoGenerator::generate_one_solution_tango()
-                ))
-            }),
+    // Boards are parsed in full before their size is checked, so large
+    // bodies are turned away up front
+    let post_routes = Router::new()
+        .route("/api/solve", post(solve))
+        .route("/api/solution", post(solution))
+        .route("/api/validate", post(validate))
+        .route("/api/validate-move", post(validate_move))
+        .route("/api/analyze", post(analyze))
+        .route("/api/autofill", post(autofill))
+        .route("/api/replay", post(replay))
+        .route("/api/restriction-graph", post(restriction_graph))
+        .route("/api/can-add-restriction", post(can_add_restriction))
+        .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
+        .layer(middleware::map_response(error::body_too_large));
+    Router::new()
+        .route("/api/tango-board", get(tango_board))
+        .route("/api/tango-board.png", get(tango_board_png))
+        .route("/api/puzzle/{id}", get(puzzle))
+        .route("/api/daily", get(daily))
+        .route("/api/queens-board", get(game_board::<Queens>))
+        .route("/api/zip-board", get(game_board::<Zip>))
+        .merge(post_routes)
+        .fallback(|| async { ApiError::not_found() })
+        .layer(CatchPanicLayer::custom(error::handle_panic))
+        .layer(cors)
+        .with_state(state)
+}
+
+/// Resolves on Ctrl-C or SIGTERM, after which the server stops accepting
+/// connections and finishes the requests in flight.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for Ctrl-C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(
+            tokio::signal::unix::SignalKind::terminate(),
         )
-        .layer(cors);
+        .expect("Failed to listen for SIGTERM")
+        .recv()
+        .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    eprintln!("Shutting down");
+}
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8081").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+/// Boards generated while looking for a puzzle of a requested difficulty.
+const DIFFICULTY_ATTEMPTS: usize = 2000;
+
+#[derive(Deserialize)]
+struct BoardQuery {
+    /// Both dimensions of a square board.
+    size: Option<usize>,
+    /// Overrides `size` for the number of columns.
+    width: Option<usize>,
+    /// Overrides `size` for the number of rows.
+    height: Option<usize>,
+    /// Attach the solution, which spoils the puzzle for the player.
+    #[serde(default)]
+    include_solution: bool,
+    difficulty: Option<DifficultyTier>,
+    /// Only return puzzles that can be solved without guessing.
+    #[serde(default)]
+    no_guess: bool,
+    /// Returns the puzzle shared as [`Tango::to_code`] instead of generating
+    /// one, ignoring the size and difficulty.
+    code: Option<String>,
+    #[serde(default)]
+    format: BoardFormat,
+}
+
+/// Shape of the `/api/tango-board` response.
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum BoardFormat {
+    /// The board with its metadata.
+    #[default]
+    Json,
+    /// The board together with its code, SVG and difficulty, saving static
+    /// site generators a request per format.
+    All,
+}
+
+impl BoardQuery {
+    /// Generator for boards of the requested size.
+    fn generator(&self, state: &AppState) -> Result<TangoGenerator, ApiError> {
+        let size = self.size.unwrap_or(6);
+        let (width, height) =
+            (self.width.unwrap_or(size), self.height.unwrap_or(size));
+        let generator = TangoGenerator::new(width, height).map_err(|err| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_size",
+                err.to_string(),
+            )
+        })?;
+        Ok(generator
+            .with_solver(state.solver.clone())
+            .with_tier_thresholds(state.thresholds))
+    }
+
+    /// New puzzle matching the size, difficulty and `no_guess`.
+    fn generate(&self, state: &AppState) -> Result<Tango, ApiError> {
+        let generator = self.generator(state)?;
+        match self.difficulty {
+            Some(_) if self.no_guess => Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_query",
+                "`difficulty` and `no_guess` can't be combined.",
+            )),
+            None if self.no_guess => Ok(generator.generate_logic_only()),
+            Some(tier) => generator
+                .generate_with_tier(tier, DIFFICULTY_ATTEMPTS)
+                .ok_or_else(|| {
+                    ApiError::new(
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "difficulty_unavailable",
+                        "No puzzle of the requested difficulty was found.",
+                    )
+                }),
+            None => Ok(generator.generate_unique()),
+        }
+    }
+}
+
+/// Facts about a generated board, e.g. for a difficulty badge.
+#[derive(Serialize)]
+struct BoardMetadata {
+    difficulty: Option<usize>,
+    unique: bool,
+    logic_only: bool,
+}
+
+impl BoardMetadata {
+    /// Only puzzles with exactly one solution are issued.
+    fn new(tango: &Tango) -> Self {
+        BoardMetadata {
+            difficulty: tango.difficulty(),
+            unique: true,
+            logic_only: tango.is_logic_only(),
+        }
+    }
+}
+
+/// The board in every format, for `format=all`.
+#[derive(Serialize)]
+struct AllFormatsResponse {
+    board: Tango,
+    code: String,
+    svg: String,
+    difficulty: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct BoardResponse {
+    /// Fetches the puzzle again from `/api/puzzle/{id}`.
+    id: Uuid,
+    #[serde(flatten)]
+    board: Tango,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solution: Option<Grid<TangoTile>>,
+    #[serde(flatten)]
+    metadata: BoardMetadata,
+}
+
+async fn tango_board(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<BoardQuery>,
+) -> Result<Response, ApiError> {
+    let decoded = match &query.code {
+        Some(code) => {
+            let tango = Tango::from_code(code).map_err(|err| {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_code",
+                    err.to_string(),
+                )
+            })?;
+            // Issued puzzles must have exactly one solution to reveal
+            unique_solution(solve_cached(&state, &tango).await?)?;
+            Some(tango)
+        }
+        None => None,
+    };
+    blocking(move || board_response(&state, &query, decoded)).await?
+}
+
+/// The `/api/tango-board` response for a decoded board, or a newly generated
+/// one. Generating and analyzing the board both run the solver, so this is
+/// called on the blocking pool.
+fn board_response(
+    state: &AppState,
+    query: &BoardQuery,
+    decoded: Option<Tango>,
+) -> Result<Response, ApiError> {
+    let tango = match decoded {
+        Some(tango) => tango,
+        // Every generator behind the endpoint checks for uniqueness
+        None => query.generate(state)?,
+    };
+    if query.format == BoardFormat::All {
+        let response = AllFormatsResponse {
+            code: tango.to_code(),
+            svg: tango.to_svg(),
+            difficulty: tango.difficulty(),
+            board: tango,
+        };
+        return Ok(Json(response).into_response());
+    }
+    let solution = query
+        .include_solution
+        .then(|| tango.solution())
+        .flatten()
+        .map(|solution| solution.grid);
+    let response = BoardResponse {
+        id: state.puzzles.insert(tango.clone()),
+        metadata: BoardMetadata::new(&tango),
+        board: tango,
+        solution,
+    };
+    Ok(Json(response).into_response())
+}
+
+async fn tango_board_png(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<BoardQuery>,
+) -> Result<Response, ApiError> {
+    let generator = query.generator(&state)?;
+    let png = blocking(move || generator.generate_unique().to_png())
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "render_failed",
+                "The board could not be rendered.",
+            )
+        })?;
+    Ok(([(header::CONTENT_TYPE, "image/png")], png).into_response())
+}
+
+async fn puzzle(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Tango>, ApiError> {
+    // Malformed ids are as unknown as expired ones
+    let id = id.parse().map_err(|_| ApiError::not_found())?;
+    state
+        .puzzles
+        .get(id)
+        .map(Json)
+        .ok_or_else(ApiError::not_found)
+}
+
+#[derive(Serialize)]
+struct DailyResponse {
+    /// UTC date of the puzzle as `yyyy-mm-dd`.
+    date: String,
+    #[serde(flatten)]
+    board: Tango,
+}
+
+/// Today's puzzle, tagged with its date and [`Tango::fingerprint`] so
+/// clients can revalidate it with `If-None-Match`.
+async fn daily(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let date = daily::today();
+    let board = state.daily.get(date, state.solver.clone()).await?;
+    let etag = format!("\"{}-{:016x}\"", date, board.fingerprint());
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+    if not_modified {
+        return Ok(
+            (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response()
+        );
+    }
+    let response = DailyResponse {
+        date: format!(
+            "{:04}-{:02}-{:02}",
+            date / 10_000,
+            date / 100 % 100,
+            date % 100
+        ),
+        board,
+    };
+    Ok(([(header::ETAG, etag)], Json(response)).into_response())
+}
+
+#[derive(Deserialize)]
+struct GameQuery {
+    size: Option<usize>,
+}
+
+/// Generates a board of any puzzle type.
+async fn game_board<G>(
+    ApiQuery(query): ApiQuery<GameQuery>,
+) -> Result<Json<G>, ApiError>
+where
+    G: Game + Send + 'static,
+    G::Error: Send + 'static,
+{
+    let size = query.size.unwrap_or(G::DEFAULT_SIZE);
+    blocking(move || G::generate(size))
+        .await?
+        .map(Json)
+        .map_err(|err| {
+            if G::gave_up(&err) {
+                ApiError::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "generation_failed",
+                    err.to_string(),
+                )
+            } else {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_size",
+                    err.to_string(),
+                )
+            }
+        })
+}
+
+/// Runs solver or generator work on the blocking thread pool, so slow boards
+/// don't hold up the runtime threads serving other requests.
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, ApiError> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|_| ApiError::internal())
+}
+
+/// Sets the flag when dropped, which happens to the handler future when the
+/// client disconnects before the response is ready.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+async fn solve(
+    State(state): State<AppState>,
+    ValidatedTango(tango): ValidatedTango,
+) -> Result<Json<Tango>, ApiError> {
+    let outcome = match state.solved.get(&tango) {
+        Some(outcome) => outcome,
+        None => {
+            let cancel = Arc::new(AtomicBool::new(false));
+            let _guard = CancelOnDrop(cancel.clone());
+            let mut board = tango.clone();
+            let outcome = tokio::task::spawn_blocking(move || {
+                board.solve_cancellable(&cancel)
+            })
+            .await
+            .map_err(|_| ApiError::internal())?;
+            state.solved.insert(&tango, outcome.clone());
+            outcome
+        }
+    };
+    unique_solution(outcome).map(Json)
+}
+
+/// The solved board of an outcome with exactly one solution.
+fn unique_solution(outcome: SolveOutcome) -> Result<Tango, ApiError> {
+    match outcome {
+        SolveOutcome::Unique(solved) => Ok(solved),
+        SolveOutcome::Multiple { .. } => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "ambiguous",
+            "The board has more than one solution.",
+        )),
+        SolveOutcome::None => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "unsolvable",
+            "The board has no solution.",
+        )),
+        SolveOutcome::Limited => Err(too_hard()),
+        // Only happens once the client is gone
+        SolveOutcome::Cancelled => Err(ApiError::internal()),
+    }
+}
+
+#[derive(Deserialize)]
+struct SolutionRequest {
+    puzzle_id: Uuid,
+    /// Must be set, so the solution is never spoiled by accident.
+    #[serde(default)]
+    confirm: bool,
+}
+
+/// Gives up on an issued puzzle. Only puzzles still in the store can be
+/// revealed, so the solution takes the random id of a puzzle the client was
+/// actually served rather than any board it makes up.
+async fn solution(
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<SolutionRequest>,
+) -> Result<Json<Tango>, ApiError> {
+    if !request.confirm {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "unconfirmed",
+            "Set `confirm` to reveal the solution.",
+        ));
+    }
+    let tango = state
+        .puzzles
+        .get(request.puzzle_id)
+        .ok_or_else(ApiError::not_found)?;
+    let solution = unique_solution(solve_cached(&state, &tango).await?)?;
+    eprintln!("Revealed the solution of puzzle {}", request.puzzle_id);
+    Ok(Json(solution))
+}
+
+#[derive(Serialize)]
+struct ValidateResponse {
+    valid: bool,
+    solved: bool,
+}
+
+async fn validate(
+    ValidatedTango(tango): ValidatedTango,
+) -> Json<ValidateResponse> {
+    Json(ValidateResponse {
+        valid: tango.is_valid(),
+        solved: tango.is_solved(),
+    })
+}
+
+#[derive(Deserialize)]
+struct MoveRequest {
+    board: Tango,
+    x: usize,
+    y: usize,
+    /// `Empty` clears the cell.
+    tile: TangoTile,
+}
+
+#[derive(Serialize)]
+struct MoveResponse {
+    /// The board after the move, kept even if it breaks a rule.
+    board: Tango,
+    #[serde(flatten)]
+    placement: PlacementResult,
+}
+
+/// Checks a single move, including the lines it completes so the UI can
+/// animate them.
+async fn validate_move(
+    ApiJson(request): ApiJson<MoveRequest>,
+) -> Result<Json<MoveResponse>, ApiError> {
+    let mut board = request.board;
+    board.check_structure().map_err(ApiError::invalid_board)?;
+    let placement = board.place_tile(request.x, request.y, request.tile);
+    Ok(Json(MoveResponse { board, placement }))
+}
+
+/// Solves a board sent by a client, bounding the work spent on boards
+/// crafted to make the search explode.
+fn solve_bounded(tango: &Tango) -> Result<SolveOutcome, ApiError> {
+    match tango.clone().solve_with_limit(CLIENT_BOARD_STEP_LIMIT) {
+        SolveOutcome::Limited => Err(too_hard()),
+        outcome => Ok(outcome),
+    }
+}
+
+/// Same as [`solve_bounded`] on the blocking pool, but remembers the outcome
+/// in the cache of `/api/solve`.
+async fn solve_cached(
+    state: &AppState,
+    tango: &Tango,
+) -> Result<SolveOutcome, ApiError> {
+    if let Some(outcome) = state.solved.get(tango) {
+        return Ok(outcome);
+    }
+    let board = tango.clone();
+    let outcome = blocking(move || solve_bounded(&board)).await??;
+    state.solved.insert(tango, outcome.clone());
+    Ok(outcome)
+}
+
+/// A board sent by a client that needs more than
+/// [`CLIENT_BOARD_STEP_LIMIT`] steps.
+fn too_hard() -> ApiError {
+    ApiError::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "too_hard",
+        "The board takes too long to analyze.",
+    )
+}
+
+async fn analyze(
+    ValidatedTango(tango): ValidatedTango,
+) -> Result<Json<PuzzleAnalysis>, ApiError> {
+    blocking(move || tango.analyze_with_limit(CLIENT_BOARD_STEP_LIMIT))
+        .await?
+        .map(Json)
+        .ok_or_else(too_hard)
+}
+
+async fn restriction_graph(
+    ValidatedTango(tango): ValidatedTango,
+) -> Json<RestrictionGraph> {
+    Json(tango.restriction_graph())
+}
+
+#[derive(Deserialize)]
+struct RestrictionRequest {
+    board: Tango,
+    restriction: TangoRestriction,
+}
+
+#[derive(Serialize)]
+struct RestrictionCheckResponse {
+    allowed: bool,
+    /// Why the restriction can't be added.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+/// Asks whether an editor may add the restriction to the board, see
+/// [`Tango::can_add_restriction`].
+async fn can_add_restriction(
+    ApiJson(request): ApiJson<RestrictionRequest>,
+) -> Result<Json<RestrictionCheckResponse>, ApiError> {
+    request
+        .board
+        .check_structure()
+        .map_err(ApiError::invalid_board)?;
+    let reason = request
+        .board
+        .can_add_restriction(&request.restriction)
+        .err()
+        .map(|err| err.to_string());
+    Ok(Json(RestrictionCheckResponse {
+        allowed: reason.is_none(),
+        reason,
+    }))
+}
+
+#[derive(Serialize)]
+struct AutofillResponse {
+    board: Tango,
+    filled: usize,
+}
+
+async fn autofill(
+    ValidatedTango(mut board): ValidatedTango,
+) -> Json<AutofillResponse> {
+    let filled = board.autofill();
+    Json(AutofillResponse { board, filled })
+}
+
+#[derive(Deserialize)]
+struct ReplayRequest {
+    puzzle: Tango,
+    /// Moves as `(x, y, tile)` in the order they were played.
+    moves: Vec<(usize, usize, TangoTile)>,
+}
+
+#[derive(Serialize)]
+struct ReplayResponse {
+    board: Tango,
+    #[serde(flatten)]
+    replay: MoveReplay,
+}
+
+async fn replay(
+    ApiJson(request): ApiJson<ReplayRequest>,
+) -> Result<Json<ReplayResponse>, ApiError> {
+    let mut board = request.puzzle;
+    board.check_structure().map_err(ApiError::invalid_board)?;
+    let replay = board.apply_moves(
+        request
+            .moves
+            .into_iter()
+            .map(|(x, y, tile)| (Coord::new(x, y), tile)),
+    );
+    Ok(Json(ReplayResponse { board, replay }))
 }
@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use ligames::{SolveOutcome, Tango};
+
+/// Outcomes of recent `/api/solve` requests by [`Tango::fingerprint`], so a
+/// board sent again isn't solved again. Holds at most `capacity` boards,
+/// dropping the least recently used one to make room.
+#[derive(Clone)]
+pub struct SolveCache {
+    inner: Arc<Mutex<Inner>>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<u64, Entry>,
+    clock: u64,
+}
+
+struct Entry {
+    /// The board itself, as different boards can share a fingerprint.
+    board: Tango,
+    outcome: SolveOutcome,
+    last_used: u64,
+}
+
+impl SolveCache {
+    pub fn new(capacity: usize) -> Self {
+        SolveCache {
+            inner: Arc::default(),
+            capacity,
+        }
+    }
+
+    pub fn get(&self, tango: &Tango) -> Option<SolveOutcome> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.clock += 1;
+        let clock = inner.clock;
+        let entry = inner
+            .entries
+            .get_mut(&tango.fingerprint())
+            .filter(|entry| entry.board == *tango)?;
+        entry.last_used = clock;
+        Some(entry.outcome.clone())
+    }
+
+    /// Remembers the outcome, unless the search was cancelled and so has no
+    /// answer. Replaces the outcome of another board with the same
+    /// fingerprint.
+    pub fn insert(&self, tango: &Tango, outcome: SolveOutcome) {
+        if self.capacity == 0 || outcome == SolveOutcome::Cancelled {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let key = tango.fingerprint();
+        if inner.entries.len() >= self.capacity
+            && !inner.entries.contains_key(&key)
+        {
+            let oldest = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                inner.entries.remove(&oldest);
+            }
+        }
+        inner.clock += 1;
+        let entry = Entry {
+            board: tango.clone(),
+            outcome,
+            last_used: inner.clock,
+        };
+        inner.entries.insert(key, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ligames::TangoTile;
+    use serde_json::json;
+
+    use super::*;
+
+    /// An otherwise empty 2x2 board with `first` in its top left cell.
+    fn board(first: TangoTile) -> Tango {
+        let tiles =
+            [first, TangoTile::Empty, TangoTile::Empty, TangoTile::Empty];
+        serde_json::from_value(json!({
+            "grid": { "width": 2, "height": 2, "tiles": tiles },
+        }))
+        .unwrap()
+    }
+
+    fn multiple(count_capped: usize) -> SolveOutcome {
+        SolveOutcome::Multiple { count_capped }
+    }
+
+    #[test]
+    fn hit() {
+        let cache = SolveCache::new(2);
+        let tango = board(TangoTile::Red);
+        cache.insert(&tango, multiple(2));
+        assert_eq!(cache.get(&tango), Some(multiple(2)));
+    }
+
+    #[test]
+    fn miss() {
+        let cache = SolveCache::new(2);
+        cache.insert(&board(TangoTile::Red), multiple(2));
+        assert_eq!(cache.get(&board(TangoTile::Blue)), None);
+    }
+
+    #[test]
+    fn fingerprint_collisions_miss() {
+        let cache = SolveCache::new(2);
+        let tango = board(TangoTile::Red);
+        // Planting a different board under the key of `tango`
+        let other = board(TangoTile::Blue);
+        cache.inner.lock().unwrap().entries.insert(
+            tango.fingerprint(),
+            Entry {
+                board: other,
+                outcome: SolveOutcome::None,
+                last_used: 0,
+            },
+        );
+        assert_eq!(cache.get(&tango), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = SolveCache::new(2);
+        let [a, b, c] =
+            [TangoTile::Red, TangoTile::Blue, TangoTile::Empty].map(board);
+        cache.insert(&a, multiple(2));
+        cache.insert(&b, SolveOutcome::None);
+        // Using `a` makes `b` the oldest
+        cache.get(&a);
+        cache.insert(&c, multiple(2));
+        assert_eq!(cache.get(&a), Some(multiple(2)));
+        assert_eq!(cache.get(&b), None);
+        assert_eq!(cache.get(&c), Some(multiple(2)));
+    }
+
+    #[test]
+    fn skips_cancelled_searches() {
+        let cache = SolveCache::new(2);
+        let tango = board(TangoTile::Red);
+        cache.insert(&tango, SolveOutcome::Cancelled);
+        assert_eq!(cache.get(&tango), None);
+    }
+}
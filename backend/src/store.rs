@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use ligames::Tango;
+use uuid::Uuid;
+
+/// Puzzles issued by the server, kept around so later requests can refer to
+/// them by id. Entries expire `ttl` after they were issued.
+#[derive(Clone)]
+pub struct PuzzleStore {
+    puzzles: Arc<Mutex<HashMap<Uuid, (Instant, Tango)>>>,
+    ttl: Duration,
+}
+
+impl PuzzleStore {
+    pub fn new(ttl: Duration) -> Self {
+        PuzzleStore {
+            puzzles: Arc::default(),
+            ttl,
+        }
+    }
+
+    pub fn insert(&self, tango: Tango) -> Uuid {
+        let id = Uuid::new_v4();
+        self.puzzles
+            .lock()
+            .unwrap()
+            .insert(id, (Instant::now(), tango));
+        id
+    }
+
+    /// The puzzle, unless it is unknown or has expired.
+    pub fn get(&self, id: Uuid) -> Option<Tango> {
+        let puzzles = self.puzzles.lock().unwrap();
+        let (issued, tango) = puzzles.get(&id)?;
+        (issued.elapsed() < self.ttl).then(|| tango.clone())
+    }
+
+    /// Drops every expired puzzle.
+    pub fn evict_expired(&self) {
+        self.puzzles
+            .lock()
+            .unwrap()
+            .retain(|_, (issued, _)| issued.elapsed() < self.ttl);
+    }
+}
@@ -0,0 +1,554 @@
+//! Requests against the whole router, without binding a socket.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use ligames::{ClientError, LigamesClient};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use super::*;
+
+fn state() -> AppState {
+    AppState {
+        solver: Arc::new(RecursiveTangoSolver::default()),
+        puzzles: PuzzleStore::new(PUZZLE_TTL),
+        daily: DailyPuzzle::default(),
+        solved: SolveCache::new(SOLVE_CACHE_SIZE),
+        thresholds: TierThresholds::default(),
+    }
+}
+
+struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl TestResponse {
+    fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+
+    /// The `code` of an [`ApiError`] body.
+    fn error_code(&self) -> String {
+        self.json()["error"]["code"].as_str().unwrap().to_owned()
+    }
+}
+
+async fn send(state: &AppState, request: Request<Body>) -> TestResponse {
+    let response = app(state.clone()).oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    TestResponse {
+        status,
+        headers,
+        body,
+    }
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+fn post_json(uri: &str, body: &Value) -> Request<Body> {
+    Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// A board from rows of `R`, `B` and `.` for empty cells.
+fn board(rows: &[&str]) -> Value {
+    let tiles: Vec<&str> = rows
+        .iter()
+        .flat_map(|row| row.chars())
+        .map(|c| match c {
+            'R' => "Red",
+            'B' => "Blue",
+            _ => "Empty",
+        })
+        .collect();
+    json!({
+        "grid": {
+            "width": rows[0].len(),
+            "height": rows.len(),
+            "tiles": tiles,
+        },
+    })
+}
+
+/// A board with an odd width, which no endpoint accepts.
+fn malformed_board() -> Value {
+    board(&["RBR", "BRB"])
+}
+
+#[tokio::test]
+async fn validated_tango_rejects_malformed_boards() {
+    let state = state();
+    let response =
+        send(&state, post_json("/api/solve", &malformed_board())).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "invalid_board");
+    // Rejected before the handler could solve and cache it
+    let tango = serde_json::from_value(malformed_board()).unwrap();
+    assert_eq!(state.solved.get(&tango), None);
+}
+
+#[tokio::test]
+async fn validate_move_rejects_malformed_boards() {
+    let request = json!({
+        "board": malformed_board(),
+        "x": 0,
+        "y": 0,
+        "tile": "Red",
+    });
+    let response =
+        send(&state(), post_json("/api/validate-move", &request)).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "invalid_board");
+}
+
+#[tokio::test]
+async fn validate_move_places_the_tile() {
+    let request = json!({
+        "board": board(&["..", ".."]),
+        "x": 1,
+        "y": 0,
+        "tile": "Blue",
+    });
+    let response =
+        send(&state(), post_json("/api/validate-move", &request)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.json()["board"]["grid"]["tiles"][1], "Blue");
+}
+
+#[tokio::test]
+async fn replay_rejects_malformed_boards() {
+    let request = json!({
+        "puzzle": malformed_board(),
+        "moves": [[0, 0, "Red"]],
+    });
+    let response = send(&state(), post_json("/api/replay", &request)).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "invalid_board");
+}
+
+#[tokio::test]
+async fn analyze_gives_up_on_boards_needing_too_many_steps() {
+    let empty = vec![".".repeat(20); 20];
+    let rows: Vec<&str> = empty.iter().map(String::as_str).collect();
+    let response =
+        send(&state(), post_json("/api/analyze", &board(&rows))).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.error_code(), "too_hard");
+}
+
+#[tokio::test]
+async fn code_round_trips_through_tango_board() {
+    let state = state();
+    let tango = TangoGenerator::new(6, 6).unwrap().generate_unique();
+    let uri = format!("/api/tango-board?code={}", tango.to_code());
+    let response = send(&state, get(&uri)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    let fetched: Tango = serde_json::from_value(body.clone()).unwrap();
+    assert_eq!(fetched, tango);
+    assert_eq!(body["unique"], true);
+    let id = body["id"].as_str().unwrap().parse().unwrap();
+    assert_eq!(state.puzzles.get(id), Some(tango));
+}
+
+#[tokio::test]
+async fn malformed_code_is_rejected() {
+    let response = send(&state(), get("/api/tango-board?code=6x6:nope")).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "invalid_code");
+}
+
+#[tokio::test]
+async fn ambiguous_code_is_rejected() {
+    let tango: Tango = serde_json::from_value(board(&["......"; 6])).unwrap();
+    let uri = format!("/api/tango-board?code={}", tango.to_code());
+    let response = send(&state(), get(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.error_code(), "ambiguous");
+}
+
+fn reveal(id: Uuid) -> Request<Body> {
+    post_json(
+        "/api/solution",
+        &json!({ "puzzle_id": id, "confirm": true }),
+    )
+}
+
+#[tokio::test]
+async fn reveals_stored_puzzles() {
+    let state = state();
+    let tango = TangoGenerator::new(6, 6).unwrap().generate_unique();
+    let id = state.puzzles.insert(tango);
+    let response = send(&state, reveal(id)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let solution: Tango = serde_json::from_value(response.json()).unwrap();
+    assert!(solution.is_solved());
+}
+
+#[tokio::test]
+async fn reveal_needs_confirmation() {
+    let state = state();
+    let tango = TangoGenerator::new(6, 6).unwrap().generate_unique();
+    let request = json!({ "puzzle_id": state.puzzles.insert(tango) });
+    let response = send(&state, post_json("/api/solution", &request)).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "unconfirmed");
+}
+
+#[tokio::test]
+async fn reveal_of_unknown_puzzle_is_not_found() {
+    let response = send(&state(), reveal(Uuid::new_v4())).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn reveal_of_ambiguous_puzzle_is_rejected() {
+    let state = state();
+    let tango =
+        serde_json::from_value(board(&["....", "....", "....", "...."]))
+            .unwrap();
+    let response = send(&state, reveal(state.puzzles.insert(tango))).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.error_code(), "ambiguous");
+}
+
+#[tokio::test]
+async fn solve_serves_cached_outcomes() {
+    let state = state();
+    let puzzle = board(&["R.", ".."]);
+    let response = send(&state, post_json("/api/solve", &puzzle)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let tango: Tango = serde_json::from_value(puzzle.clone()).unwrap();
+    assert!(state.solved.get(&tango).is_some());
+    // A planted outcome shows the second request isn't solved again
+    state.solved.insert(&tango, SolveOutcome::None);
+    let response = send(&state, post_json("/api/solve", &puzzle)).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.error_code(), "unsolvable");
+}
+
+#[tokio::test]
+async fn daily_honors_if_none_match() {
+    let state = state();
+    let response = send(&state, get("/api/daily")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let etag = response.headers[header::ETAG].clone();
+    let request = Request::get("/api/daily")
+        .header(header::IF_NONE_MATCH, etag.clone())
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&state, request).await;
+    assert_eq!(response.status, StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers[header::ETAG], etag);
+    assert!(response.body.is_empty());
+}
+
+#[tokio::test]
+async fn daily_ignores_other_etags() {
+    let request = Request::get("/api/daily")
+        .header(header::IF_NONE_MATCH, "\"stale\"")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&state(), request).await;
+    assert_eq!(response.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn analyze_reports_unique_puzzles() {
+    let response =
+        send(&state(), post_json("/api/analyze", &board(&["R.", ".."]))).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let analysis = response.json();
+    assert_eq!(analysis["unique"], true);
+    assert_eq!(analysis["satisfiable"], true);
+    assert_eq!(analysis["solution_count_capped"], 1);
+    assert!(analysis["difficulty"].is_u64());
+}
+
+#[tokio::test]
+async fn analyze_reports_ambiguous_puzzles() {
+    let response =
+        send(&state(), post_json("/api/analyze", &board(&["..", ".."]))).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let analysis = response.json();
+    assert_eq!(analysis["unique"], false);
+    assert_eq!(analysis["satisfiable"], true);
+    assert_eq!(analysis["solution_count_capped"], 2);
+    assert!(analysis["difficulty"].is_null());
+}
+
+#[tokio::test]
+async fn invalid_json_gets_a_structured_error() {
+    let request = Request::post("/api/solve")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{\"grid\":"))
+        .unwrap();
+    let response = send(&state(), request).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "invalid_json");
+    assert!(response.json()["error"]["message"].is_string());
+}
+
+#[tokio::test]
+async fn board_png_is_a_png() {
+    let response = send(&state(), get("/api/tango-board.png?size=4")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers[header::CONTENT_TYPE], "image/png");
+    assert!(response.body.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert!(response.body.len() > 100);
+}
+
+#[tokio::test]
+async fn included_solution_solves_the_puzzle() {
+    let response = send(
+        &state(),
+        get("/api/tango-board?size=6&include_solution=true"),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    let puzzle: Tango = serde_json::from_value(body.clone()).unwrap();
+    let solution: Grid<TangoTile> =
+        serde_json::from_value(body["solution"].clone()).unwrap();
+    for (given, solved) in puzzle.grid.tiles.iter().zip(&solution.tiles) {
+        assert!(*given == TangoTile::Empty || given == solved);
+    }
+    let mut solved = puzzle;
+    solved.grid = solution;
+    assert!(solved.is_solved());
+}
+
+#[tokio::test]
+async fn replay_reports_when_the_board_was_solved() {
+    let request = json!({
+        "puzzle": board(&["R.", ".."]),
+        "moves": [[1, 0, "Blue"], [0, 1, "Blue"], [1, 1, "Red"]],
+    });
+    let response = send(&state(), post_json("/api/replay", &request)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    assert_eq!(body["solved_at"], 2);
+    assert_eq!(body["illegal_move"], Value::Null);
+    assert_eq!(
+        body["board"]["grid"]["tiles"],
+        json!(["Red", "Blue", "Blue", "Red"])
+    );
+}
+
+#[tokio::test]
+async fn replay_stops_at_an_illegal_move() {
+    let request = json!({
+        "puzzle": board(&["R.", ".."]),
+        "moves": [[0, 1, "Blue"], [1, 0, "Red"], [1, 1, "Red"]],
+    });
+    let response = send(&state(), post_json("/api/replay", &request)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    assert_eq!(body["solved_at"], Value::Null);
+    assert_eq!(body["illegal_move"], 1);
+    assert_eq!(
+        body["board"]["grid"]["tiles"],
+        json!(["Red", "Empty", "Blue", "Empty"])
+    );
+}
+
+#[tokio::test]
+async fn easy_boards_score_below_medium() {
+    let state = state();
+    for _ in 0..3 {
+        let uri = "/api/tango-board?size=6&difficulty=easy";
+        let response = send(&state, get(uri)).await;
+        assert_eq!(response.status, StatusCode::OK);
+        let tango: Tango = serde_json::from_value(response.json()).unwrap();
+        let score = tango.difficulty().unwrap();
+        assert!(score < state.thresholds.medium, "score {}", score);
+        assert_eq!(response.json()["difficulty"], score);
+    }
+}
+
+#[tokio::test]
+async fn no_guess_boards_are_completed_by_autofill() {
+    let state = state();
+    for _ in 0..3 {
+        let response =
+            send(&state, get("/api/tango-board?size=6&no_guess=true")).await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.json()["logic_only"], true);
+        let mut tango: Tango = serde_json::from_value(response.json()).unwrap();
+        tango.autofill();
+        assert!(tango.is_solved());
+    }
+}
+
+#[tokio::test]
+async fn no_guess_cannot_be_combined_with_a_difficulty() {
+    let uri = "/api/tango-board?no_guess=true&difficulty=hard";
+    let response = send(&state(), get(uri)).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.error_code(), "invalid_query");
+}
+
+#[tokio::test]
+async fn restriction_graph_splits_disjoint_chains() {
+    let mut request = board(&["....", "....", "....", "...."]);
+    request["restrictions"] = json!([
+        TangoRestriction::Same(Coord::new(0, 0), Coord::new(1, 0)),
+        TangoRestriction::Different(Coord::new(1, 0), Coord::new(2, 0)),
+        TangoRestriction::Same(Coord::new(0, 3), Coord::new(1, 3)),
+    ]);
+    let response =
+        send(&state(), post_json("/api/restriction-graph", &request)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let graph: RestrictionGraph =
+        serde_json::from_value(response.json()).unwrap();
+    assert_eq!(graph.nodes.len(), 16);
+    assert_eq!(graph.edges.len(), 3);
+    assert_eq!(
+        graph.components,
+        [
+            vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)],
+            vec![Coord::new(0, 3), Coord::new(1, 3)],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn issued_puzzles_are_fetched_until_evicted() {
+    let state = AppState {
+        puzzles: PuzzleStore::new(Duration::from_millis(50)),
+        ..state()
+    };
+    let issued = send(&state, get("/api/tango-board?size=4")).await.json();
+    let uri = format!("/api/puzzle/{}", issued["id"].as_str().unwrap());
+
+    let response = send(&state, get(&uri)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let fetched: Tango = serde_json::from_value(response.json()).unwrap();
+    assert_eq!(fetched, serde_json::from_value(issued).unwrap());
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    state.puzzles.evict_expired();
+    let response = send(&state, get(&uri)).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    assert_eq!(response.error_code(), "not_found");
+}
+
+#[tokio::test]
+async fn board_metadata_matches_a_fresh_analysis() {
+    let response = send(&state(), get("/api/tango-board?size=6")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    let tango: Tango = serde_json::from_value(body.clone()).unwrap();
+    let analysis = tango.analyze();
+    assert_eq!(body["unique"], analysis.unique);
+    assert_eq!(body["difficulty"], json!(analysis.difficulty));
+    assert_eq!(body["logic_only"], tango.is_logic_only());
+}
+
+#[tokio::test]
+async fn game_board_serves_tango() {
+    let app =
+        Router::new().route("/game", axum::routing::get(game_board::<Tango>));
+    let response = app.oneshot(get("/game?size=4")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let tango: Tango = serde_json::from_slice(&body).unwrap();
+    assert_eq!((tango.grid.width, tango.grid.height), (4, 4));
+    let solution = Game::solve(&tango).unwrap();
+    assert!(Game::is_solved(&solution));
+    assert_eq!(tango.count_solutions(2), 1);
+}
+
+#[tokio::test]
+async fn client_talks_to_the_server() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app(state())).await.unwrap();
+    });
+    let client = LigamesClient::new(format!("http://{}", address));
+
+    let tango = client.fetch_board(4).await.unwrap();
+    assert_eq!((tango.grid.width, tango.grid.height), (4, 4));
+    let analysis = client.analyze(&tango).await.unwrap();
+    assert!(analysis.unique);
+    let solved = client.solve(&tango).await.unwrap();
+    assert!(solved.is_solved());
+
+    let ambiguous: Tango =
+        serde_json::from_value(board(&["..", ".."])).unwrap();
+    match client.solve(&ambiguous).await {
+        Err(ClientError::Api { status, code, .. }) => {
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(code, "ambiguous");
+        }
+        other => panic!("expected an API error, got {:?}", other),
+    }
+    server.abort();
+}
+
+#[tokio::test]
+async fn tango_board_generates_non_square_boards() {
+    let uri = "/api/tango-board?width=4&height=6";
+    let response = send(&state(), get(uri)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let tango: Tango = serde_json::from_value(response.json()).unwrap();
+    assert_eq!((tango.grid.width, tango.grid.height), (4, 6));
+    assert_eq!(tango.count_solutions(2), 1);
+}
+
+#[tokio::test]
+async fn oversized_bodies_are_rejected() {
+    let tiles = vec!["Empty"; MAX_BODY_SIZE / 4];
+    let request = json!({
+        "grid": { "width": tiles.len(), "height": 1, "tiles": tiles },
+    });
+    let response = send(&state(), post_json("/api/solve", &request)).await;
+    assert_eq!(response.status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.error_code(), "body_too_large");
+}
+
+#[tokio::test]
+async fn all_formats_describe_the_same_board() {
+    let response =
+        send(&state(), get("/api/tango-board?size=6&format=all")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    let board: Tango = serde_json::from_value(body["board"].clone()).unwrap();
+    let decoded = Tango::from_code(body["code"].as_str().unwrap()).unwrap();
+    assert_eq!(decoded.grid, board.grid);
+    assert_eq!(decoded.restrictions(), board.restrictions());
+    assert_eq!(body["svg"], board.to_svg());
+    assert_eq!(body["difficulty"], json!(board.difficulty()));
+    assert!(body["difficulty"].is_u64());
+}
+
+#[tokio::test]
+async fn serve_returns_after_a_shutdown_signal() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let (shutdown, signal) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(serve(listener, app(state()), async {
+        signal.await.ok();
+    }));
+    let client = LigamesClient::new(format!("http://{}", address));
+    assert!(client.fetch_board(4).await.is_ok());
+
+    shutdown.send(()).unwrap();
+    let served = tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("the server kept running after the signal")
+        .unwrap();
+    assert!(served.is_ok());
+}
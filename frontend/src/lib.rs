@@ -1,4 +1,4 @@
-use ligames::{Tango, TangoRestriction, TangoTile};
+use ligames::{Coord, Tango, TangoRestriction, TangoTile, Theme, Violation};
 use reqwasm::http::Request;
 use web_sys::console;
 use yew::prelude::*;
@@ -39,38 +39,114 @@ pub fn app() -> Html {
 #[derive(Properties, PartialEq)]
 struct BoardProps {
     board: Tango,
+    #[prop_or_default]
+    theme: Theme,
 }
 
 struct Board {
     board: Tango,
+    focus: Focus,
+    /// Rules broken by the current board, refreshed after every move.
+    violations: Vec<Violation>,
 }
 
-struct TileClick {
+/// Cell selected for keyboard input.
+#[derive(Clone, Copy, PartialEq)]
+struct Focus {
     row: usize,
     col: usize,
 }
 
+enum Msg {
+    TileClick { row: usize, col: usize },
+    KeyDown(KeyboardEvent),
+}
+
+impl Board {
+    fn set_focused_tile(&mut self, tile: TangoTile) {
+        let Focus { row, col } = self.focus;
+        // Cycling visits every tile state, so at most two steps are needed
+        for _ in 0..2 {
+            if self.board.get_tile((col, row)) == Some(tile) {
+                break;
+            }
+            self.board.cycle_tile((col, row));
+        }
+    }
+
+    /// Symbol of the restriction between two neighboring cells, if any.
+    fn connector(
+        &self,
+        a: impl Into<Coord>,
+        b: impl Into<Coord>,
+    ) -> &'static str {
+        let (a, b) = (a.into(), b.into());
+        self.board
+            .restrictions_for_cell(a.x, a.y)
+            .into_iter()
+            .find(|r| r.cells() == (a, b) || r.cells() == (b, a))
+            .map_or("", |r| match r {
+                TangoRestriction::Same(_, _) => "=",
+                TangoRestriction::Different(_, _) => "×",
+            })
+    }
+
+    /// Moves the focus by the given offset, clamped to the board edges.
+    fn move_focus(&mut self, d_row: isize, d_col: isize) {
+        let clamp = |value: usize, delta: isize, len: usize| {
+            value.saturating_add_signed(delta).min(len - 1)
+        };
+        self.focus = Focus {
+            row: clamp(self.focus.row, d_row, self.board.grid.height),
+            col: clamp(self.focus.col, d_col, self.board.grid.width),
+        };
+    }
+}
+
 impl Component for Board {
-    type Message = TileClick;
+    type Message = Msg;
     type Properties = BoardProps;
 
     fn create(ctx: &Context<Self>) -> Self {
+        let board = ctx.props().board.clone();
         Self {
-            board: ctx.props().board.clone(),
+            violations: board.violations(),
+            board,
+            focus: Focus { row: 0, col: 0 },
         }
     }
 
-    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        self.board.cycle_tile(msg.col, msg.row);
-        console::log_1(
-            &format!(
-                "Clicked on tile ({}, {}) - {:?}",
-                msg.row,
-                msg.col,
-                self.board.get_tile(msg.row, msg.col)
-            )
-            .into(),
-        );
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::TileClick { row, col } => {
+                self.focus = Focus { row, col };
+                self.board.cycle_tile((col, row));
+                console::log_1(
+                    &format!(
+                        "Clicked on tile ({}, {}) - {:?}",
+                        row,
+                        col,
+                        self.board.get_tile((col, row))
+                    )
+                    .into(),
+                );
+            }
+            Msg::KeyDown(event) => {
+                match event.key().as_str() {
+                    "ArrowUp" => self.move_focus(-1, 0),
+                    "ArrowDown" => self.move_focus(1, 0),
+                    "ArrowLeft" => self.move_focus(0, -1),
+                    "ArrowRight" => self.move_focus(0, 1),
+                    "r" | "R" => self.set_focused_tile(TangoTile::Red),
+                    "b" | "B" => self.set_focused_tile(TangoTile::Blue),
+                    " " => self.set_focused_tile(TangoTile::Empty),
+                    _ => return false,
+                }
+                // Keep arrows and space from scrolling the page
+                event.prevent_default();
+            }
+        }
+        self.violations = self.board.violations();
         true
     }
 
@@ -78,57 +154,36 @@ impl Component for Board {
         let width = self.board.grid.width;
         let height = self.board.grid.height;
 
-        // Prepare tiles as a 2D vector for easy indexing
-        let mut tiles_2d: Vec<Vec<&TangoTile>> = Vec::new();
-        for row in 0..height {
-            tiles_2d.push(
-                (0..width)
-                    .map(|col| &self.board.grid.tiles[row * width + col])
-                    .collect(),
-            );
-        }
-
         // Build the grid with optional connectors
         let mut grid_html = Vec::new();
 
         for row in 0..height {
             let mut row_html = Vec::new();
             for col in 0..width {
-                let onclick = ctx
-                    .link()
-                    .callback(move |_event: MouseEvent| TileClick { row, col });
+                let onclick = ctx.link().callback(move |_event: MouseEvent| {
+                    Msg::TileClick { row, col }
+                });
 
                 // TangoTile
-                let tile = tiles_2d[row][col];
-                let label = match tile {
-                    TangoTile::Empty => "⬜",
-                    TangoTile::Red => "🟥",
-                    TangoTile::Blue => "🟦",
-                };
+                let tile = self.board.get_tile((col, row)).unwrap_or_default();
+                let theme = &ctx.props().theme;
+                let label = theme.glyph(tile).to_string();
+                let style = format!("background-color: {};", theme.color(tile));
+                let violated = self.violations.iter().any(|violation| {
+                    violation.cells.contains(&Coord::new(col, row))
+                });
+                let class = classes!(
+                    "tile",
+                    (self.focus == Focus { row, col }).then_some("focused"),
+                    violated.then_some("violation")
+                );
                 row_html.push(
-                    html! { <div class="tile" {onclick} >{ label }</div> },
+                    html! { <div {class} {style} {onclick} >{ label }</div> },
                 );
 
                 // Horizontal restriction
                 if col + 1 < width {
-                    let mut conn = "".to_string();
-                    for r in &self.board.restrictions {
-                        match r {
-                            TangoRestriction::Same(a, b)
-                                if (a.0, a.1) == (row, col)
-                                    && (b.0, b.1) == (row, col + 1) =>
-                            {
-                                conn = "=".to_string()
-                            }
-                            TangoRestriction::Different(a, b)
-                                if (a.0, a.1) == (row, col)
-                                    && (b.0, b.1) == (row, col + 1) =>
-                            {
-                                conn = "×".to_string()
-                            }
-                            _ => {}
-                        }
-                    }
+                    let conn = self.connector((col, row), (col + 1, row));
                     if !conn.is_empty() {
                         row_html.push(
                             html! { <div class="connector">{ conn }</div> },
@@ -141,28 +196,10 @@ impl Component for Board {
 
             grid_html.push(html! { <div style={format!("display:grid; grid-template-columns: repeat({}, 40px 20px); gap:4px;", width)}>{ row_html }</div>});
             row_html = Vec::new();
-            // Optional: add a row of vertical connectors here if needed
-            // TODO: Implement vertical connectors between rows
+            // Connectors between this row and the next
             if row + 1 < height {
                 for col in 0..width {
-                    let mut conn = "".to_string();
-                    for r in &self.board.restrictions {
-                        match r {
-                            TangoRestriction::Same(a, b)
-                                if (a.0, a.1) == (row, col)
-                                    && (b.0, b.1) == (row + 1, col) =>
-                            {
-                                conn = "=".to_string()
-                            }
-                            TangoRestriction::Different(a, b)
-                                if (a.0, a.1) == (row, col)
-                                    && (b.0, b.1) == (row + 1, col) =>
-                            {
-                                conn = "×".to_string()
-                            }
-                            _ => {}
-                        }
-                    }
+                    let conn = self.connector((col, row), (col, row + 1));
                     row_html.push(
                     html! { <div class="connector-vertical">{ conn }</div> },
                 );
@@ -179,13 +216,22 @@ impl Component for Board {
             grid_html.push(html! { <div style={format!("display:grid; grid-template-columns: repeat({}, 40px 20px); gap:4px;", width)}>{ row_html }</div>});
         }
 
+        let onkeydown = ctx.link().callback(Msg::KeyDown);
+
         html! {
             <div>
                 <div
                     class="grid"
+                    tabindex="0"
+                    {onkeydown}
                 >
                     { for grid_html }
                 </div>
+                <ul class="violations">
+                    { for self.violations.iter().map(|violation| html! {
+                        <li>{ violation.to_string() }</li>
+                    }) }
+                </ul>
             </div>
         }
     }
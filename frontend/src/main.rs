@@ -1,6 +1,4 @@
-mod lib;
-
 fn main() {
     // Mount the Yew app into the <body>
-    yew::Renderer::<lib::App>::new().render();
+    yew::Renderer::<ligames_frontend::App>::new().render();
 }
@@ -0,0 +1,49 @@
+//! Generates a puzzle, solves it and prints both, without any server.
+//!
+//! Usage: `demo [SIZE] [--watch]`. With `--watch` a new puzzle is generated
+//! on every Enter until the input ends.
+
+use std::io::BufRead;
+
+use ligames::TangoGenerator;
+
+fn main() {
+    let mut size = 6;
+    let mut watch = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--watch" => watch = true,
+            _ => match arg.parse() {
+                Ok(parsed) => size = parsed,
+                Err(_) => {
+                    eprintln!("Usage: demo [SIZE] [--watch]");
+                    std::process::exit(2);
+                }
+            },
+        }
+    }
+    let generator = match TangoGenerator::new(size, size) {
+        Ok(generator) => generator,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        let tango = generator.generate_unique();
+        println!("Puzzle:\n{}", tango);
+        match tango.solution() {
+            Some(solution) => println!("Solution:\n{}", solution),
+            None => println!("No solution found."),
+        }
+        if !watch {
+            break;
+        }
+        println!("Press Enter for another puzzle, Ctrl-D to quit.");
+        if !matches!(lines.next(), Some(Ok(_))) {
+            break;
+        }
+    }
+}
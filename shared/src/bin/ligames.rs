@@ -0,0 +1,122 @@
+//! Command line tools around the puzzles.
+//!
+//! `ligames dump [--count N] [--size S]` prints `N` unique `S`x`S` puzzles as
+//! NDJSON, one `{"puzzle", "solution", "difficulty"}` object per line. They
+//! are generated on every core and written as they come, so the output can
+//! be as large as needed.
+//!
+//! `ligames calibrate [--count N] [--size S]` prints difficulty tier
+//! thresholds measured on `N` unique `S`x`S` puzzles as JSON, which the
+//! server reads from the file named by `LIGAMES_TIER_THRESHOLDS`.
+
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use ligames::{Grid, Tango, TangoGenerator, TangoTile};
+use serde::Serialize;
+
+const USAGE: &str = "Usage: ligames dump|calibrate [--count N] [--size S]";
+
+#[derive(Serialize)]
+struct DumpLine {
+    puzzle: Tango,
+    solution: Grid<TangoTile>,
+    difficulty: Option<usize>,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.split_first() {
+        Some((command, options)) if command == "dump" => dump(options),
+        Some((command, options)) if command == "calibrate" => {
+            calibrate(options)
+        }
+        _ => exit_with(USAGE),
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2);
+}
+
+/// The `--count` and `--size` options, defaulting to 100 puzzles of 6x6.
+fn parse_options(options: &[String]) -> (usize, usize) {
+    let mut count = 100;
+    let mut size = 6;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let value = options.next().and_then(|value| value.parse().ok());
+        match (option.as_str(), value) {
+            ("--count", Some(value)) => count = value,
+            ("--size", Some(value)) => size = value,
+            _ => exit_with(USAGE),
+        }
+    }
+    (count, size)
+}
+
+fn calibrate(options: &[String]) {
+    let (count, size) = parse_options(options);
+    let thresholds = TangoGenerator::new(size, size)
+        .unwrap_or_else(|err| exit_with(&err.to_string()))
+        .calibrate_tiers(count)
+        .unwrap_or_else(|| exit_with("Needs at least one puzzle."));
+    println!(
+        "{}",
+        serde_json::to_string(&thresholds).expect("Thresholds serialize")
+    );
+}
+
+fn dump(options: &[String]) {
+    let (count, size) = parse_options(options);
+    let generator = TangoGenerator::new(size, size)
+        .unwrap_or_else(|err| exit_with(&err.to_string()));
+
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let remaining = AtomicUsize::new(count);
+    // Bounded, so slow output holds back generation instead of piling up
+    let (lines, received) = mpsc::sync_channel::<String>(workers * 4);
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            let lines = lines.clone();
+            let (generator, remaining) = (&generator, &remaining);
+            scope.spawn(move || {
+                // Claims one puzzle at a time until all are taken
+                while remaining
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                        n.checked_sub(1)
+                    })
+                    .is_ok()
+                {
+                    let puzzle = generator.generate_unique();
+                    let solution = puzzle
+                        .solution()
+                        .expect("Generated puzzles are solvable")
+                        .grid;
+                    let line = DumpLine {
+                        difficulty: puzzle.difficulty(),
+                        puzzle,
+                        solution,
+                    };
+                    let line = serde_json::to_string(&line)
+                        .expect("Puzzles serialize to JSON");
+                    if lines.send(line).is_err() {
+                        return; // The writer is gone
+                    }
+                }
+            });
+        }
+        drop(lines);
+
+        let mut out = BufWriter::new(std::io::stdout().lock());
+        for line in received {
+            if writeln!(out, "{}", line).is_err() {
+                // E.g. a closed pipe, the workers stop on their next send
+                return;
+            }
+        }
+        let _ = out.flush();
+    });
+}
@@ -0,0 +1,113 @@
+use std::fmt::Display;
+
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::{PuzzleAnalysis, Tango};
+
+/// Talks to a running ligames server from native code, e.g.
+/// `LigamesClient::new("http://localhost:8081")`.
+#[derive(Debug, Clone)]
+pub struct LigamesClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The server could not be reached or sent something unreadable.
+    Http(reqwest::Error),
+    /// The server answered with one of its documented errors.
+    Api {
+        status: StatusCode,
+        code: String,
+        message: String,
+    },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Http(err) => write!(f, "Request failed: {}", err),
+            ClientError::Api {
+                status,
+                code,
+                message,
+            } => write!(f, "Server error {} ({}): {}", status, code, message),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Http(err)
+    }
+}
+
+/// Body of every error response.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    code: String,
+    message: String,
+}
+
+impl LigamesClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        LigamesClient {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Sends the request and reads the JSON body, turning error responses
+    /// into [`ClientError::Api`].
+    async fn send<T: DeserializeOwned>(
+        request: RequestBuilder,
+    ) -> Result<T, ClientError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+        let body: ErrorBody = response.json().await?;
+        Err(ClientError::Api {
+            status,
+            code: body.error.code,
+            message: body.error.message,
+        })
+    }
+
+    /// A new puzzle with a unique solution from `/api/tango-board`.
+    pub async fn fetch_board(&self, size: usize) -> Result<Tango, ClientError> {
+        let request = self
+            .http
+            .get(self.url("/api/tango-board"))
+            .query(&[("size", size)]);
+        Self::send(request).await
+    }
+
+    /// The board solved by the server, which fails for boards without
+    /// exactly one solution.
+    pub async fn solve(&self, tango: &Tango) -> Result<Tango, ClientError> {
+        Self::send(self.http.post(self.url("/api/solve")).json(tango)).await
+    }
+
+    pub async fn analyze(
+        &self,
+        tango: &Tango,
+    ) -> Result<PuzzleAnalysis, ClientError> {
+        Self::send(self.http.post(self.url("/api/analyze")).json(tango)).await
+    }
+}
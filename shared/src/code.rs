@@ -0,0 +1,214 @@
+use std::fmt::Display;
+
+use crate::{BoardKind, Coord, Tango, TangoError, TangoRestriction, TangoTile};
+
+/// Reasons a puzzle code can't be read by [`Tango::from_code`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    /// The code is not `WxH:cells[:restrictions]`.
+    Format,
+    /// The number of cells does not match the dimensions.
+    CellCount(usize),
+    Cell(char),
+    Restriction(String),
+    InvalidGiven(Coord),
+    /// The number of bytes of [`Tango::from_solution_bits`] does not match
+    /// the dimensions.
+    ByteCount(usize),
+    Tango(TangoError),
+}
+
+impl Display for CodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodeError::Format => {
+                write!(f, "Expected a code like `6x6:<cells>:<restrictions>`.")
+            }
+            CodeError::CellCount(count) => {
+                write!(
+                    f,
+                    "Found {} cells, which does not match the size.",
+                    count
+                )
+            }
+            CodeError::Cell(cell) => write!(f, "Unknown cell {:?}.", cell),
+            CodeError::Restriction(restriction) => {
+                write!(f, "Unknown restriction {:?}.", restriction)
+            }
+            CodeError::InvalidGiven(cell) => {
+                write!(f, "Given at {} breaks the rules.", cell)
+            }
+            CodeError::ByteCount(count) => write!(
+                f,
+                "Found {} bytes, which does not match the size.",
+                count
+            ),
+            CodeError::Tango(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CodeError::Tango(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<TangoError> for CodeError {
+    fn from(err: TangoError) -> Self {
+        CodeError::Tango(err)
+    }
+}
+
+impl Tango {
+    /// Short text form of the puzzle for sharing in links, e.g.
+    /// `4x4:r..b........b..r:0s_5D`. The cells follow the size row by row as
+    /// `.`, `r` or `b`. Each restriction is the index of its top or left
+    /// cell followed by `s` (same) or `d` (different) for the cell to its
+    /// right and `S` or `D` for the cell below. Only uses characters that
+    /// need no escaping in URLs. The rules are not included.
+    pub fn to_code(&self) -> String {
+        let cells: String = self
+            .grid
+            .iter()
+            .map(|tile| match tile {
+                TangoTile::Empty => '.',
+                TangoTile::Red => 'r',
+                TangoTile::Blue => 'b',
+            })
+            .collect();
+        let mut code =
+            format!("{}x{}:{}", self.grid.width, self.grid.height, cells);
+        let restrictions: Vec<String> = self
+            .restrictions
+            .iter()
+            .map(|restriction| {
+                let (a, b) = restriction.cells();
+                let (first, second) = if (a.y, a.x) < (b.y, b.x) {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                let same = matches!(restriction, TangoRestriction::Same(..));
+                let kind = match (same, second.y > first.y) {
+                    (true, false) => 's',
+                    (false, false) => 'd',
+                    (true, true) => 'S',
+                    (false, true) => 'D',
+                };
+                format!("{}{}", first.y * self.grid.width + first.x, kind)
+            })
+            .collect();
+        if !restrictions.is_empty() {
+            code.push(':');
+            code.push_str(&restrictions.join("_"));
+        }
+        code
+    }
+
+    /// Reads a puzzle written by [`Tango::to_code`].
+    pub fn from_code(code: &str) -> Result<Tango, CodeError> {
+        let mut parts = code.split(':');
+        let (width, height) = parts
+            .next()
+            .and_then(|size| size.split_once('x'))
+            .and_then(|(width, height)| {
+                Some((width.parse().ok()?, height.parse().ok()?))
+            })
+            .ok_or(CodeError::Format)?;
+        let cells = parts.next().ok_or(CodeError::Format)?;
+        let restrictions = parts.next().unwrap_or_default();
+        if parts.next().is_some() {
+            return Err(CodeError::Format);
+        }
+        let mut tango = Tango::new(width, height, vec![])?;
+
+        if !restrictions.is_empty() {
+            for restriction in restrictions.split('_') {
+                let invalid = || CodeError::Restriction(restriction.into());
+                let split = restriction.len().saturating_sub(1);
+                let (index, kind) =
+                    restriction.split_at_checked(split).ok_or_else(invalid)?;
+                let index: usize = index.parse().map_err(|_| invalid())?;
+                let a = Coord::new(index % width, index / width);
+                let (b, same) = match kind {
+                    "s" => (Coord::new(a.x + 1, a.y), true),
+                    "d" => (Coord::new(a.x + 1, a.y), false),
+                    "S" => (Coord::new(a.x, a.y + 1), true),
+                    "D" => (Coord::new(a.x, a.y + 1), false),
+                    _ => return Err(invalid()),
+                };
+                tango.add_restriction(if same {
+                    TangoRestriction::Same(a, b)
+                } else {
+                    TangoRestriction::Different(a, b)
+                })?;
+            }
+        }
+
+        let count = cells.chars().count();
+        if count != width * height {
+            return Err(CodeError::CellCount(count));
+        }
+        for (i, cell) in cells.chars().enumerate() {
+            let tile = match cell {
+                '.' => continue,
+                'r' => TangoTile::Red,
+                'b' => TangoTile::Blue,
+                _ => return Err(CodeError::Cell(cell)),
+            };
+            let coord = Coord::new(i % width, i / width);
+            if !tango.set_tile(coord, tile) {
+                return Err(CodeError::InvalidGiven(coord));
+            }
+        }
+        Ok(tango)
+    }
+
+    /// The tiles of a full board in as few bytes as possible, e.g. to store
+    /// many solutions: the width and the height, then one bit per cell row
+    /// by row, `0` for red and `1` for blue, starting at the highest bit of
+    /// each byte. `None` while a cell is empty. Restrictions and rules are
+    /// left out.
+    pub fn solution_bits(&self) -> Option<Vec<u8>> {
+        let cells = self.grid.tiles.len();
+        let mut bits = vec![
+            u8::try_from(self.grid.width).ok()?,
+            u8::try_from(self.grid.height).ok()?,
+        ];
+        bits.resize(2 + cells.div_ceil(8), 0);
+        for (i, tile) in self.grid.iter().enumerate() {
+            match tile {
+                TangoTile::Empty => return None,
+                TangoTile::Red => {}
+                TangoTile::Blue => bits[2 + i / 8] |= 0x80 >> (i % 8),
+            }
+        }
+        Some(bits)
+    }
+
+    /// Reads a board written by [`Tango::solution_bits`]. The tiles are not
+    /// checked against the rules.
+    pub fn from_solution_bits(bits: &[u8]) -> Result<Tango, CodeError> {
+        let [width, height, cells @ ..] = bits else {
+            return Err(CodeError::ByteCount(bits.len()));
+        };
+        let (width, height) = (*width as usize, *height as usize);
+        let mut tango = Tango::new(width, height, vec![])?;
+        if cells.len() != (width * height).div_ceil(8) {
+            return Err(CodeError::ByteCount(bits.len()));
+        }
+        for (i, tile) in tango.grid.tiles.iter_mut().enumerate() {
+            *tile = if cells[i / 8] & (0x80 >> (i % 8)) == 0 {
+                TangoTile::Red
+            } else {
+                TangoTile::Blue
+            };
+        }
+        tango.kind = BoardKind::Solution;
+        Ok(tango)
+    }
+}
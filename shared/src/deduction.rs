@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Coord, Tango, TangoRestriction, TangoTile};
+
+/// A single tile placed while solving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveStep {
+    pub x: usize,
+    pub y: usize,
+    pub tile: TangoTile,
+}
+
+/// Technique needed to deduce a step, from the easiest to the hardest.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum DeductionLevel {
+    /// Follows from the tile counts and the no-three-in-a-row rule of the
+    /// cell's row or column.
+    Line,
+    /// Follows from a single restriction with an already filled partner.
+    Restriction,
+    /// Follows from a chain of restrictions leading to a filled cell.
+    Chain,
+    /// Not deducible with the techniques above, taken from the solution.
+    Guess,
+}
+
+impl Tango {
+    /// Solves the board one step at a time, always taking the easiest
+    /// available deduction, and returns the steps with their technique.
+    /// Stops early if the board gets stuck without a solution to guess from.
+    pub fn solve_graded_steps(&mut self) -> Vec<(SolveStep, DeductionLevel)> {
+        let solution = self.solution();
+        let mut steps = Vec::new();
+        while let Some((step, level)) = self.next_graded_step(&solution) {
+            if !self.set_tile((step.x, step.y), step.tile) {
+                break; // The board contradicts itself
+            }
+            steps.push((step, level));
+        }
+        steps
+    }
+
+    fn next_graded_step(
+        &mut self,
+        solution: &Option<Tango>,
+    ) -> Option<(SolveStep, DeductionLevel)> {
+        let empty: Vec<_> = (0..self.grid.height)
+            .flat_map(|y| (0..self.grid.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.get_tile((x, y)) == Some(TangoTile::Empty))
+            .collect();
+        let first = *empty.first()?;
+
+        type Deduction = fn(&mut Tango, usize, usize) -> Option<TangoTile>;
+        let deductions: [(DeductionLevel, Deduction); 3] = [
+            (DeductionLevel::Line, Tango::line_deduction),
+            (DeductionLevel::Restriction, Tango::restriction_deduction),
+            (DeductionLevel::Chain, Tango::chain_deduction),
+        ];
+        for (level, deduce) in deductions {
+            for &(x, y) in &empty {
+                if let Some(tile) = deduce(self, x, y) {
+                    return Some((SolveStep { x, y, tile }, level));
+                }
+            }
+        }
+
+        let (x, y) = first;
+        let tile = solution.as_ref()?.get_tile((x, y))?;
+        Some((SolveStep { x, y, tile }, DeductionLevel::Guess))
+    }
+
+    /// The only color allowed by the row and column of the cell, ignoring
+    /// restrictions.
+    fn line_deduction(&mut self, x: usize, y: usize) -> Option<TangoTile> {
+        let red = self.line_allows(x, y, TangoTile::Red);
+        let blue = self.line_allows(x, y, TangoTile::Blue);
+        match (red, blue) {
+            (true, false) => Some(TangoTile::Red),
+            (false, true) => Some(TangoTile::Blue),
+            _ => None,
+        }
+    }
+
+    fn line_allows(&mut self, x: usize, y: usize, tile: TangoTile) -> bool {
+        if let Some(cell) = self.grid.get_mut(x, y) {
+            *cell = tile;
+        }
+        let valid = self.is_valid_row(y) && self.is_valid_column(x);
+        self.clear_tile((x, y));
+        valid
+    }
+
+    fn restriction_deduction(
+        &mut self,
+        x: usize,
+        y: usize,
+    ) -> Option<TangoTile> {
+        self.restrictions.iter().find_map(|restriction| {
+            let (a, b) = restriction.cells();
+            let cell = Coord::new(x, y);
+            let partner = if a == cell {
+                b
+            } else if b == cell {
+                a
+            } else {
+                return None;
+            };
+            let tile = self.get_tile(partner)?;
+            match restriction {
+                _ if tile == TangoTile::Empty => None,
+                TangoRestriction::Same(_, _) => Some(tile),
+                TangoRestriction::Different(_, _) => tile.opposite(),
+            }
+        })
+    }
+
+    fn chain_deduction(&mut self, x: usize, y: usize) -> Option<TangoTile> {
+        self.restriction_components()
+            .into_iter()
+            .find(|component| component.contains(&Coord::new(x, y)))?
+            .into_iter()
+            .find_map(|other| {
+                let tile = self.get_tile(other)?;
+                if tile == TangoTile::Empty {
+                    return None;
+                }
+                if self.must_match((x, y), other)? {
+                    Some(tile)
+                } else {
+                    tile.opposite()
+                }
+            })
+    }
+}
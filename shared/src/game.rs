@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{
+    GenerationError, Queens, QueensError, QueensGenerator, Tango,
+    TangoGenerator, Zip, ZipError, ZipGenerator,
+};
+
+/// What every puzzle type offers, so frontends and endpoints can handle
+/// them alike.
+pub trait Game: Serialize + Sized {
+    type Solution;
+    type Error: Display;
+
+    /// Size of the board when none is asked for.
+    const DEFAULT_SIZE: usize;
+
+    /// Generates a square puzzle with exactly one solution.
+    fn generate(size: usize) -> Result<Self, Self::Error>;
+
+    /// Whether [`Game::generate`] failed for a valid size, e.g. because it
+    /// ran out of attempts, so trying again may succeed.
+    fn gave_up(_error: &Self::Error) -> bool {
+        false
+    }
+
+    fn solve(&self) -> Option<Self::Solution>;
+
+    fn is_solved(&self) -> bool;
+}
+
+impl Game for Tango {
+    type Solution = Tango;
+    type Error = GenerationError;
+
+    const DEFAULT_SIZE: usize = 6;
+
+    fn generate(size: usize) -> Result<Self, Self::Error> {
+        Ok(TangoGenerator::new(size, size)?.generate_unique())
+    }
+
+    fn solve(&self) -> Option<Self::Solution> {
+        self.solution()
+    }
+
+    fn is_solved(&self) -> bool {
+        Tango::is_solved(self)
+    }
+}
+
+impl Game for Queens {
+    type Solution = Queens;
+    type Error = QueensError;
+
+    const DEFAULT_SIZE: usize = 8;
+
+    fn generate(size: usize) -> Result<Self, Self::Error> {
+        QueensGenerator::new(size).generate_unique()
+    }
+
+    fn gave_up(error: &Self::Error) -> bool {
+        matches!(error, QueensError::Attempts(_))
+    }
+
+    fn solve(&self) -> Option<Self::Solution> {
+        self.solution()
+    }
+
+    fn is_solved(&self) -> bool {
+        Queens::is_solved(self)
+    }
+}
+
+impl Game for Zip {
+    type Solution = Zip;
+    type Error = ZipError;
+
+    const DEFAULT_SIZE: usize = 6;
+
+    fn generate(size: usize) -> Result<Self, Self::Error> {
+        ZipGenerator::new(size).generate_unique()
+    }
+
+    fn solve(&self) -> Option<Self::Solution> {
+        self.solution()
+    }
+
+    fn is_solved(&self) -> bool {
+        Zip::is_solved(self)
+    }
+}
@@ -1,18 +1,132 @@
-use std::{collections::BTreeSet, fmt::Display};
+use std::cmp::Ordering;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
-use rand::seq::IteratorRandom;
-use rand::{random_bool, Rng};
+use rand::rngs::StdRng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg(feature = "client")]
+mod client;
+mod code;
+mod deduction;
+mod game;
+mod linkedin;
+pub mod named_coord;
+pub mod nullable_tile;
+mod queens;
+mod render;
+mod sat;
+mod solver;
+mod stepper;
+mod symmetry;
+mod violation;
+mod zip;
+
+#[cfg(feature = "client")]
+pub use client::{ClientError, LigamesClient};
+pub use code::CodeError;
+pub use deduction::{DeductionLevel, SolveStep};
+pub use game::Game;
+pub use linkedin::ImportError;
+pub use queens::{
+    Queens, QueensError, QueensGenerator, QUEENS_MAX_SIZE, QUEENS_MIN_SIZE,
+};
+pub use render::Theme;
+pub use solver::{BranchOrder, RecursiveTangoSolver, TangoSolver};
+pub use stepper::{SolverSnapshot, SteppingSolver};
+pub use violation::{Line, PlacementResult, Violation, ViolationKind};
+pub use zip::{Zip, ZipError, ZipGenerator, ZIP_MAX_SIZE, ZIP_MIN_SIZE};
+
+/// A Tango board. In JSON only `grid` is required, so clients written before
+/// the other fields existed keep working: missing `rules` are the classic
+/// ones and a missing `kind` is `InProgress`. `restrictions` are left out
+/// when there are none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tango {
     pub grid: Grid<TangoTile>,
-    pub restrictions: Vec<TangoRestriction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    restrictions: Vec<TangoRestriction>,
+    #[serde(default)]
+    pub rules: TangoRules,
+    #[serde(default)]
+    pub kind: BoardKind,
+}
+
+/// What a board is meant as, so clients know how to present it. Boards don't
+/// remember their givens, so this is recorded where the board comes from.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+pub enum BoardKind {
+    /// A fresh puzzle as generated or imported.
+    Puzzle,
+    /// Any other state, e.g. while being played.
+    #[default]
+    InProgress,
+    /// Filled in by a solver or completed by playing.
+    Solution,
+}
+
+/// Optional rule variants on top of classic Tango.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct TangoRules {
+    /// Also forbid three same tiles in a line along both diagonals. Only
+    /// checked by full-board validation, not on every placement.
+    #[serde(default)]
+    pub check_diagonals: bool,
+    /// Longest allowed run of one color along a row.
+    #[serde(default = "default_max_run")]
+    pub row_max_run: usize,
+    /// Longest allowed run of one color along a column.
+    #[serde(default = "default_max_run")]
+    pub col_max_run: usize,
+}
+
+fn default_max_run() -> usize {
+    2
+}
+
+impl Default for TangoRules {
+    fn default() -> Self {
+        TangoRules {
+            check_diagonals: false,
+            row_max_run: default_max_run(),
+            col_max_run: default_max_run(),
+        }
+    }
 }
 
+/// Tiles are ordered `Empty < Red < Blue`.
 #[derive(
-    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
 )]
 #[serde(rename_all = "PascalCase")]
 pub enum TangoTile {
@@ -22,20 +136,380 @@ pub enum TangoTile {
     Blue,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl TangoTile {
+    /// The other color, `None` for an empty tile.
+    pub fn opposite(self) -> Option<TangoTile> {
+        match self {
+            TangoTile::Red => Some(TangoTile::Blue),
+            TangoTile::Blue => Some(TangoTile::Red),
+            TangoTile::Empty => None,
+        }
+    }
+}
+
+/// Position of a cell, `x` being the column and `y` the row. Serialized as
+/// an `[x, y]` pair, see [`named_coord`] for `{ "x": .., "y": .. }`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[serde(from = "(usize, usize)", into = "(usize, usize)")]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Coord { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((x, y): (usize, usize)) -> Self {
+        Coord { x, y }
+    }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(coord: Coord) -> Self {
+        (coord.x, coord.y)
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[serde(rename_all = "PascalCase")]
 pub enum TangoRestriction {
-    Same((usize, usize), (usize, usize)),
-    Different((usize, usize), (usize, usize)),
+    Same(Coord, Coord),
+    Different(Coord, Coord),
+}
+
+impl TangoRestriction {
+    /// The two cells the restriction connects.
+    pub fn cells(&self) -> (Coord, Coord) {
+        match self {
+            TangoRestriction::Same(a, b)
+            | TangoRestriction::Different(a, b) => (*a, *b),
+        }
+    }
+
+    /// The same restriction with its cells in ascending order, so equal
+    /// restrictions compare equal however their cells were listed.
+    fn normalized(&self) -> TangoRestriction {
+        let (a, b) = self.cells();
+        let (a, b) = (a.min(b), a.max(b));
+        match self {
+            TangoRestriction::Same(..) => TangoRestriction::Same(a, b),
+            TangoRestriction::Different(..) => {
+                TangoRestriction::Different(a, b)
+            }
+        }
+    }
+
+    /// The same kind of restriction between the cells `map` moves its own
+    /// cells to.
+    fn map_cells(&self, map: impl Fn(Coord) -> Coord) -> TangoRestriction {
+        match *self {
+            TangoRestriction::Same(a, b) => {
+                TangoRestriction::Same(map(a), map(b))
+            }
+            TangoRestriction::Different(a, b) => {
+                TangoRestriction::Different(map(a), map(b))
+            }
+        }
+    }
+}
+
+/// Whether the tiles around a restriction currently follow it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestrictionStatus {
+    Satisfied,
+    Violated,
+    /// At least one of the cells is still empty.
+    Pending,
+}
+
+/// Restrictions grouped by their kind, serialized as
+/// `{ "same": [[a, b], ...], "different": [[a, b], ...] }` for renderers that
+/// draw each kind of connector in one pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestrictionsByKind {
+    pub same: Vec<(Coord, Coord)>,
+    pub different: Vec<(Coord, Coord)>,
+}
+
+impl<'a> FromIterator<&'a TangoRestriction> for RestrictionsByKind {
+    fn from_iter<I: IntoIterator<Item = &'a TangoRestriction>>(
+        iter: I,
+    ) -> Self {
+        let mut by_kind = RestrictionsByKind::default();
+        for restriction in iter {
+            match restriction {
+                TangoRestriction::Same(a, b) => by_kind.same.push((*a, *b)),
+                TangoRestriction::Different(a, b) => {
+                    by_kind.different.push((*a, *b))
+                }
+            }
+        }
+        by_kind
+    }
+}
+
+impl From<RestrictionsByKind> for Vec<TangoRestriction> {
+    fn from(by_kind: RestrictionsByKind) -> Self {
+        let same = by_kind
+            .same
+            .into_iter()
+            .map(|(a, b)| TangoRestriction::Same(a, b));
+        let different = by_kind
+            .different
+            .into_iter()
+            .map(|(a, b)| TangoRestriction::Different(a, b));
+        same.chain(different).collect()
+    }
+}
+
+/// Restrictions in canonical form: each connects its cells in ascending
+/// order, the list is sorted and no pair of cells appears twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestrictionSet {
+    restrictions: Vec<TangoRestriction>,
+}
+
+impl RestrictionSet {
+    /// Collects the restrictions, dropping exact duplicates (in either cell
+    /// order) and failing if two of them connect the same cells with
+    /// different kinds.
+    pub fn try_from_iter(
+        iter: impl IntoIterator<Item = TangoRestriction>,
+    ) -> Result<Self, TangoError> {
+        let mut by_cells = BTreeMap::new();
+        for restriction in iter {
+            let restriction = restriction.normalized();
+            let (a, b) = restriction.cells();
+            match by_cells.entry((a, b)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(restriction);
+                }
+                Entry::Occupied(entry) if *entry.get() == restriction => {}
+                Entry::Occupied(_) => {
+                    return Err(TangoError::ContradictingRestriction(a, b));
+                }
+            }
+        }
+        Ok(RestrictionSet {
+            restrictions: by_cells.into_values().collect(),
+        })
+    }
+
+    pub fn as_slice(&self) -> &[TangoRestriction] {
+        &self.restrictions
+    }
+
+    pub fn into_vec(self) -> Vec<TangoRestriction> {
+        self.restrictions
+    }
+}
+
+/// Largest supported width or height, keeping the exponential solver and the
+/// grid allocation bounded.
+pub const MAX_DIMENSION: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TangoError {
+    ZeroDimension,
+    OddDimension,
+    TooLarge,
+    OutOfBounds(Coord),
+    NotAdjacent(Coord, Coord),
+    DuplicateRestriction(Coord, Coord),
+    ContradictingRestriction(Coord, Coord),
+    /// The row at this index differs in length from the first row.
+    JaggedRow(usize),
+    /// Two boards that should match have these `(width, height)`s.
+    DimensionMismatch((usize, usize), (usize, usize)),
+    /// The grid has this many tiles, which doesn't match its dimensions.
+    TileCount(usize),
+    /// The tiles already placed on these cells break the restriction.
+    BrokenRestriction(Coord, Coord),
+    /// A restriction between these cells closes a cycle of restrictions
+    /// that can't all hold, see [`Tango::restrictions_satisfiable`].
+    ContradictingCycle(Coord, Coord),
+}
+
+impl Display for TangoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TangoError::ZeroDimension => {
+                write!(f, "Width and height must be greater than zero.")
+            }
+            TangoError::OddDimension => {
+                write!(f, "Width and height must be even numbers.")
+            }
+            TangoError::TooLarge => {
+                write!(f, "Width and height must be at most {}.", MAX_DIMENSION)
+            }
+            TangoError::OutOfBounds(cell) => {
+                write!(f, "Cell {} is out of bounds.", cell)
+            }
+            TangoError::NotAdjacent(a, b) => {
+                write!(f, "Cells {} and {} are not adjacent.", a, b)
+            }
+            TangoError::DuplicateRestriction(a, b) => {
+                write!(f, "Cells {} and {} are already restricted.", a, b)
+            }
+            TangoError::JaggedRow(y) => {
+                write!(f, "Row {} differs in length from the first row.", y)
+            }
+            TangoError::ContradictingRestriction(a, b) => write!(
+                f,
+                "Cells {} and {} have a contradicting restriction.",
+                a, b
+            ),
+            TangoError::DimensionMismatch((w1, h1), (w2, h2)) => write!(
+                f,
+                "Boards of size {}x{} and {}x{} can't be compared.",
+                w1, h1, w2, h2
+            ),
+            TangoError::TileCount(count) => write!(
+                f,
+                "Found {} tiles, which does not match the size.",
+                count
+            ),
+            TangoError::BrokenRestriction(a, b) => write!(
+                f,
+                "The tiles on cells {} and {} break the restriction.",
+                a, b
+            ),
+            TangoError::ContradictingCycle(a, b) => write!(
+                f,
+                "Cells {} and {} close a cycle of contradicting restrictions.",
+                a, b
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TangoError {}
+
+/// Reasons a puzzle can't be generated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GenerationError {
+    /// No board can have the requested dimensions.
+    Tango(TangoError),
+    /// [`GeneratorConfig::same_ratio`] is not a number.
+    SameRatio(f64),
+    /// The board meant as the solution is not a solved board.
+    NotSolved,
+    /// Both restriction kinds are required, but the same ratio of this
+    /// [`GeneratorConfig::same_ratio`] only generates one of them.
+    RestrictionKinds(f64),
+}
+
+impl Display for GenerationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationError::Tango(err) => write!(f, "{}", err),
+            GenerationError::SameRatio(ratio) => {
+                write!(f, "Same ratio {} is not a number.", ratio)
+            }
+            GenerationError::NotSolved => {
+                write!(f, "The target board is not a valid solution.")
+            }
+            GenerationError::RestrictionKinds(ratio) => write!(
+                f,
+                "Same ratio {} can't generate both kinds of restrictions.",
+                ratio
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GenerationError::Tango(err) => Some(err),
+            GenerationError::SameRatio(_)
+            | GenerationError::NotSolved
+            | GenerationError::RestrictionKinds(_) => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl From<TangoError> for GenerationError {
+    fn from(err: TangoError) -> Self {
+        GenerationError::Tango(err)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Grid<T> {
     pub width: usize,
     pub height: usize,
     pub tiles: Vec<T>,
 }
 
+impl Tango {
+    /// The restrictions with their cells in ascending order, sorted, so
+    /// boards listing the same restrictions differently compare equal.
+    fn sorted_restrictions(&self) -> Vec<TangoRestriction> {
+        let mut restrictions: Vec<TangoRestriction> = self
+            .restrictions
+            .iter()
+            .map(TangoRestriction::normalized)
+            .collect();
+        restrictions.sort_unstable();
+        restrictions
+    }
+}
+
+/// Boards are equal when they have the same tiles, rules and kind and the
+/// same restrictions, in any order and with their cells in any order.
+impl PartialEq for Tango {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Tango {}
+
+/// Orders boards by their dimensions, then by their tiles and then by their
+/// sorted restrictions, e.g. to pick a canonical board among symmetric ones.
+impl Ord for Tango {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.grid.width, self.grid.height)
+            .cmp(&(other.grid.width, other.grid.height))
+            .then_with(|| self.grid.tiles.cmp(&other.grid.tiles))
+            .then_with(|| {
+                self.sorted_restrictions().cmp(&other.sorted_restrictions())
+            })
+            .then_with(|| self.rules.cmp(&other.rules))
+            .then_with(|| self.kind.cmp(&other.kind))
+    }
+}
+
+impl PartialOrd for Tango {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Display for TangoTile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -48,19 +522,37 @@ impl Display for TangoTile {
 
 impl Display for Tango {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_board(f, false, false)
+    }
+}
+
+impl Tango {
+    /// Draws the board with box-drawing characters, optionally coloring the
+    /// tiles with ANSI escapes and marking each restriction with its
+    /// [`RestrictionStatus`].
+    fn write_board(
+        &self,
+        f: &mut impl std::fmt::Write,
+        colored: bool,
+        with_status: bool,
+    ) -> std::fmt::Result {
         writeln!(f, "Tango Puzzle: {}x{}", self.grid.width, self.grid.height)?;
         writeln!(f, "Restrictions:")?;
         for restriction in &self.restrictions {
+            if with_status {
+                let marker = match self.restriction_status(restriction) {
+                    RestrictionStatus::Satisfied => "✓",
+                    RestrictionStatus::Violated => "✗",
+                    RestrictionStatus::Pending => "…",
+                };
+                write!(f, "{} ", marker)?;
+            }
             match restriction {
-                TangoRestriction::Same((x1, y1), (x2, y2)) => {
-                    writeln!(f, "Same: ({}, {}) <-> ({}, {})", x1, y1, x2, y2)?;
+                TangoRestriction::Same(a, b) => {
+                    writeln!(f, "Same: {} <-> {}", a, b)?;
                 }
-                TangoRestriction::Different((x1, y1), (x2, y2)) => {
-                    writeln!(
-                        f,
-                        "Different: ({}, {}) <-> ({}, {})",
-                        x1, y1, x2, y2
-                    )?;
+                TangoRestriction::Different(a, b) => {
+                    writeln!(f, "Different: {} <-> {}", a, b)?;
                 }
             }
         }
@@ -72,22 +564,38 @@ impl Display for Tango {
         let tr = "┐"; // U+2510
         let bl = "└"; // U+2514
         let br = "┘"; // U+2518
-        let t_down = "┬"; // U+252C
-        let t_up = "┴"; // U+2534
-        let t_right = "├"; // U+251C
-        let t_left = "┤"; // U+2524
         let cross = "┼"; // U+253C
-        writeln!(f, "{}{}{}", tl, h.repeat(self.grid.width * 2 - 1), tr)?;
-        for y in 0..self.grid.height {
+        writeln!(
+            f,
+            "{}{}{}",
+            tl,
+            h.repeat((self.grid.width * 2).saturating_sub(1)),
+            tr
+        )?;
+        // A board without columns has no cells to draw in its rows either
+        let rows = if self.grid.width == 0 {
+            0
+        } else {
+            self.grid.height
+        };
+        for y in 0..rows {
             write!(f, "{}", v)?;
             for x in 0..self.grid.width {
                 if let Some(tile) = self.grid.get(x, y) {
-                    write!(f, "{}", tile)?;
+                    match tile {
+                        TangoTile::Red if colored => {
+                            write!(f, "\x1b[31m{}\x1b[0m", tile)?
+                        }
+                        TangoTile::Blue if colored => {
+                            write!(f, "\x1b[34m{}\x1b[0m", tile)?
+                        }
+                        _ => write!(f, "{}", tile)?,
+                    }
                 } else {
                     write!(f, "",)?; // Empty space for out-of-bounds
                 }
                 // Add = or x between tiles with Same or Different restrictions
-                if x < self.grid.width - 1 {
+                if x + 1 < self.grid.width {
                     match self.get_restriction((x, y), (x + 1, y)) {
                         Some(TangoRestriction::Same(_, _)) => write!(f, "=")?,
                         Some(TangoRestriction::Different(_, _)) => {
@@ -100,7 +608,7 @@ impl Display for Tango {
                 }
             }
             writeln!(f)?;
-            if y < self.grid.height - 1 {
+            if y + 1 < rows {
                 write!(f, "{}", v)?;
                 for x in 0..self.grid.width {
                     // Add = or x between tiles with Same or Different restrictions
@@ -111,18 +619,191 @@ impl Display for Tango {
                         }
                         None => write!(f, "{}", h)?,
                     }
-                    if x < self.grid.width - 1 {
+                    if x + 1 < self.grid.width {
                         write!(f, "{}", cross)?; // Space between tiles
                     }
                 }
                 writeln!(f, "{}", v)?;
             }
         }
-        writeln!(f, "{}{}{}", bl, h.repeat(self.grid.width * 2 - 1), br)?;
+        writeln!(
+            f,
+            "{}{}{}",
+            bl,
+            h.repeat((self.grid.width * 2).saturating_sub(1)),
+            br
+        )?;
         Ok(())
     }
 }
 
+impl<T> Grid<T> {
+    /// Iterates over the tiles in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.tiles.iter()
+    }
+
+    /// Sets every tile to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.tiles.fill(value);
+    }
+
+    /// Number of tiles matching `pred`.
+    pub fn count(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.tiles.iter().filter(|tile| pred(tile)).count()
+    }
+
+    /// Every pair of orthogonally adjacent cells, i.e. every place a
+    /// restriction can go. Pairs are ordered row-major by their first cell,
+    /// the right neighbor coming before the one below.
+    pub fn edges(&self) -> impl Iterator<Item = (Coord, Coord)> {
+        let (width, height) = (self.width, self.height);
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+            .flat_map(move |a| {
+                let right = (a.x + 1 < width).then(|| Coord::new(a.x + 1, a.y));
+                let down = (a.y + 1 < height).then(|| Coord::new(a.x, a.y + 1));
+                right.into_iter().chain(down).map(move |b| (a, b))
+            })
+    }
+
+    /// Iterates over the tiles in row-major order together with their
+    /// coordinates.
+    pub fn iter_coords(&self) -> impl Iterator<Item = (Coord, &T)> {
+        let width = self.width;
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(move |(i, tile)| (Coord::new(i % width, i / width), tile))
+    }
+
+    /// Row-major index of the tile, the only place bounds are checked.
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// The tile at `(x, y)`, or `None` outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.tiles.get(self.index(x, y)?)
+    }
+
+    /// The tiles above, right of, below and left of `(x, y)`, in that order,
+    /// with `None` past the edges of the grid.
+    pub fn neighbors(&self, x: usize, y: usize) -> [Option<&T>; 4] {
+        [
+            y.checked_sub(1).and_then(|y| self.get(x, y)),
+            self.get(x + 1, y),
+            self.get(x, y + 1),
+            x.checked_sub(1).and_then(|x| self.get(x, y)),
+        ]
+    }
+
+    /// The cells above, right of, below and left of `coord`, in that order,
+    /// leaving out those past the edges of the grid.
+    pub fn neighbor_coords(
+        &self,
+        Coord { x, y }: Coord,
+    ) -> impl Iterator<Item = Coord> {
+        let (width, height) = (self.width, self.height);
+        [
+            y.checked_sub(1).map(|y| Coord::new(x, y)),
+            (x + 1 < width).then(|| Coord::new(x + 1, y)),
+            (y + 1 < height).then(|| Coord::new(x, y + 1)),
+            x.checked_sub(1).map(|x| Coord::new(x, y)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// The tiles at `a` and `b`, or `None` if either is outside of the grid.
+    pub fn get_pair(
+        &self,
+        (ax, ay): (usize, usize),
+        (bx, by): (usize, usize),
+    ) -> Option<(&T, &T)> {
+        Some((self.get(ax, ay)?, self.get(bx, by)?))
+    }
+
+    /// The tile at `(x, y)`, or `None` outside of the grid.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        let index = self.index(x, y)?;
+        self.tiles.get_mut(index)
+    }
+
+    /// Swaps the tiles at `a` and `b`. Returns `false` without changing
+    /// anything if either is outside of the grid.
+    pub fn swap(
+        &mut self,
+        (ax, ay): (usize, usize),
+        (bx, by): (usize, usize),
+    ) -> bool {
+        let (Some(a), Some(b)) = (self.index(ax, ay), self.index(bx, by))
+        else {
+            return false;
+        };
+        self.tiles.swap(a, b);
+        true
+    }
+
+    /// Swaps two rows. Returns `false` without changing anything if either
+    /// is outside of the grid.
+    pub fn swap_rows(&mut self, y1: usize, y2: usize) -> bool {
+        if y1 >= self.height || y2 >= self.height {
+            return false;
+        }
+        for x in 0..self.width {
+            self.tiles.swap(y1 * self.width + x, y2 * self.width + x);
+        }
+        true
+    }
+
+    /// Swaps two columns. Returns `false` without changing anything if
+    /// either is outside of the grid.
+    pub fn swap_columns(&mut self, x1: usize, x2: usize) -> bool {
+        if x1 >= self.width || x2 >= self.width {
+            return false;
+        }
+        for y in 0..self.height {
+            self.tiles.swap(y * self.width + x1, y * self.width + x2);
+        }
+        true
+    }
+}
+
+/// Builds a grid from its rows, which all have to be of the same, non-zero
+/// length.
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = TangoError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(TangoError::ZeroDimension);
+        }
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(TangoError::JaggedRow(y));
+        }
+        Ok(Grid {
+            width,
+            height,
+            tiles: rows.into_iter().flatten().collect(),
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<T: Default + Clone> Grid<T> {
     fn new(width: usize, height: usize) -> Self {
         let tiles = vec![T::default(); width * height];
@@ -132,20 +813,6 @@ impl<T: Default + Clone> Grid<T> {
             tiles,
         }
     }
-    fn get(&self, x: usize, y: usize) -> Option<&T> {
-        if x < self.width && y < self.height {
-            Some(&self.tiles[y * self.width + x])
-        } else {
-            None
-        }
-    }
-    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
-        if x < self.width && y < self.height {
-            Some(self.tiles.get_mut(y * self.width + x).unwrap())
-        } else {
-            None
-        }
-    }
 }
 
 impl Tango {
@@ -153,20 +820,53 @@ impl Tango {
         width: usize,
         height: usize,
         restrictions: Vec<TangoRestriction>,
-    ) -> Result<Self, &'static str> {
-        if width == 0 || height == 0 {
-            return Err("Width and height must be greater than zero.");
-        }
-        if width % 2 != 0 || height % 2 != 0 {
-            return Err("Width and height must be even numbers.");
-        }
+    ) -> Result<Self, TangoError> {
+        Tango::check_dimensions(width, height)?;
         Ok(Tango {
             grid: Grid::new(width, height),
             restrictions,
+            rules: TangoRules::default(),
+            kind: BoardKind::Puzzle,
         })
     }
 
-    fn set_tile(&mut self, x: usize, y: usize, tile: TangoTile) -> bool {
+    /// Checks that a board of the given size can be created.
+    pub fn check_dimensions(
+        width: usize,
+        height: usize,
+    ) -> Result<(), TangoError> {
+        if width == 0 || height == 0 {
+            return Err(TangoError::ZeroDimension);
+        }
+        if !width.is_multiple_of(2) || !height.is_multiple_of(2) {
+            return Err(TangoError::OddDimension);
+        }
+        if width > MAX_DIMENSION || height > MAX_DIMENSION {
+            return Err(TangoError::TooLarge);
+        }
+        Ok(())
+    }
+
+    /// Checks what deserializing a board doesn't: its dimensions, its
+    /// number of tiles and that every restriction could have been added with
+    /// [`Tango::add_restriction`]. The tiles may still break rules.
+    pub fn check_structure(&self) -> Result<(), TangoError> {
+        let (width, height) = (self.grid.width, self.grid.height);
+        let mut empty = Tango::new(width, height, vec![])?;
+        if self.grid.tiles.len() != width * height {
+            return Err(TangoError::TileCount(self.grid.tiles.len()));
+        }
+        for restriction in &self.restrictions {
+            empty.add_restriction(restriction.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Places the tile unless it would break a rule, returning whether it was
+    /// placed.
+    #[must_use]
+    fn set_tile(&mut self, coord: impl Into<Coord>, tile: TangoTile) -> bool {
+        let Coord { x, y } = coord.into();
         let mut prev_tile = TangoTile::Empty;
         if let Some(existing_tile) = self.grid.get_mut(x, y) {
             prev_tile = *existing_tile;
@@ -185,7 +885,15 @@ impl Tango {
         }
     }
 
-    pub fn cycle_tile(&mut self, x: usize, y: usize) {
+    fn clear_tile(&mut self, coord: impl Into<Coord>) {
+        let Coord { x, y } = coord.into();
+        if let Some(tile) = self.grid.get_mut(x, y) {
+            *tile = TangoTile::Empty;
+        }
+    }
+
+    pub fn cycle_tile(&mut self, coord: impl Into<Coord>) {
+        let Coord { x, y } = coord.into();
         if let Some(existing_tile) = self.grid.get_mut(x, y) {
             let new_tile = match *existing_tile {
                 TangoTile::Empty => TangoTile::Red,
@@ -194,59 +902,546 @@ impl Tango {
             };
             *existing_tile = new_tile;
         }
+        self.mark_played();
     }
 
-    pub fn get_tile(&self, x: usize, y: usize) -> Option<TangoTile> {
-        self.grid.get(x, y).cloned()
+    /// Records that the board was played on, completing it or not.
+    fn mark_played(&mut self) {
+        self.kind = if self.is_solved() {
+            BoardKind::Solution
+        } else {
+            BoardKind::InProgress
+        };
     }
 
-    fn is_valid_row(&self, y: usize) -> bool {
-        if y >= self.grid.height {
-            return false;
-        }
-        let mut last_tile = TangoTile::Empty;
-        let mut consecuteive_same_count = 0;
-        let mut red_count = 0;
-        let mut blue_count = 0;
-        for x in 0..self.grid.width {
-            match self.get_tile(x, y) {
-                Some(TangoTile::Red) => red_count += 1,
-                Some(TangoTile::Blue) => blue_count += 1,
-                _ => {}
+    /// Plays the moves in order, stopping at the first one that is out of
+    /// bounds or breaks the rules. Placing `Empty` clears a cell.
+    pub fn apply_moves(
+        &mut self,
+        moves: impl IntoIterator<Item = (Coord, TangoTile)>,
+    ) -> MoveReplay {
+        let mut replay = MoveReplay::default();
+        for (index, (coord, tile)) in moves.into_iter().enumerate() {
+            if !self.set_tile(coord, tile) {
+                replay.illegal_move = Some(index);
+                break;
             }
-            if let Some(tile) = self.get_tile(x, y) {
-                if tile != TangoTile::Empty && tile == last_tile {
-                    consecuteive_same_count += 1;
-                    if consecuteive_same_count > 1 {
-                        return false; // More than one consecutive same tile
-                    }
-                } else {
-                    consecuteive_same_count = 0; // Reset count for different tile
-                }
-                last_tile = tile;
+            if replay.solved_at.is_none() && self.is_solved() {
+                replay.solved_at = Some(index);
             }
         }
-        red_count <= self.grid.width / 2 && blue_count <= self.grid.width / 2
+        self.mark_played();
+        replay
     }
-    fn is_valid_column(&self, x: usize) -> bool {
-        if x >= self.grid.width {
-            return false;
+
+    /// Fills in every cell that is forced by the current state of the board
+    /// and returns how many cells were filled. Never guesses.
+    pub fn autofill(&mut self) -> usize {
+        let mut filled = 0;
+        while let Some((x, y, tile)) = self.forced_cell() {
+            let placed = self.set_tile((x, y), tile);
+            debug_assert!(placed, "A forced tile always fits");
+            filled += 1;
         }
-        let mut last_tile = TangoTile::Empty;
-        let mut consecuteive_same_count = 0;
-        let mut red_count = 0;
-        let mut blue_count = 0;
+        self.mark_played();
+        filled
+    }
+
+    /// Finds an empty cell where only one of the colors can be placed.
+    fn forced_cell(&mut self) -> Option<(usize, usize, TangoTile)> {
         for y in 0..self.grid.height {
-            match self.get_tile(x, y) {
-                Some(TangoTile::Red) => red_count += 1,
-                Some(TangoTile::Blue) => blue_count += 1,
-                _ => {}
+            for x in 0..self.grid.width {
+                if self.get_tile((x, y)) != Some(TangoTile::Empty) {
+                    continue;
+                }
+                let red = self.fits(x, y, TangoTile::Red);
+                let blue = self.fits(x, y, TangoTile::Blue);
+                match (red, blue) {
+                    (true, false) => return Some((x, y, TangoTile::Red)),
+                    (false, true) => return Some((x, y, TangoTile::Blue)),
+                    _ => {}
+                }
             }
-            if let Some(tile) = self.get_tile(x, y) {
+        }
+        None
+    }
+
+    /// Checks whether `tile` can be placed on the empty cell at `(x, y)`.
+    fn fits(&mut self, x: usize, y: usize, tile: TangoTile) -> bool {
+        if self.set_tile((x, y), tile) {
+            self.clear_tile((x, y));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The structural part of the puzzle: same dimensions, rules and
+    /// restrictions with an empty grid. Boards don't remember which tiles
+    /// were given, so every tile is cleared.
+    pub fn puzzle_skeleton(&self) -> Tango {
+        Tango {
+            grid: Grid::new(self.grid.width, self.grid.height),
+            restrictions: self.restrictions.clone(),
+            rules: self.rules,
+            kind: BoardKind::Puzzle,
+        }
+    }
+
+    /// Stable content hash over the dimensions, rules, tiles and the
+    /// restrictions, independent of the order the restrictions are stored in.
+    pub fn fingerprint(&self) -> u64 {
+        let mut restrictions: Vec<_> = self
+            .restrictions
+            .iter()
+            .map(|restriction| {
+                let (a, b) = restriction.cells();
+                let kind = match restriction {
+                    TangoRestriction::Same(_, _) => 0,
+                    TangoRestriction::Different(_, _) => 1,
+                };
+                (a.min(b), a.max(b), kind)
+            })
+            .collect();
+        restrictions.sort_unstable();
+
+        let mut hash = Fnv1a::new();
+        hash.write(self.grid.width as u64);
+        hash.write(self.grid.height as u64);
+        hash.write(self.rules.check_diagonals as u64);
+        hash.write(self.rules.row_max_run as u64);
+        hash.write(self.rules.col_max_run as u64);
+        for tile in &self.grid {
+            hash.write(*tile as u64);
+        }
+        for (a, b, kind) in restrictions {
+            for value in [a.x, a.y, b.x, b.y, kind] {
+                hash.write(value as u64);
+            }
+        }
+        hash.finish()
+    }
+
+    /// Counts the solutions of the board, stopping at `cap`.
+    pub fn count_solutions(&self, cap: usize) -> usize {
+        RecursiveTangoSolver::default().count_capped(self, cap)
+    }
+
+    /// Returns the first solution found, or `None` if the board is
+    /// unsolvable.
+    pub fn solution(&self) -> Option<Tango> {
+        RecursiveTangoSolver::default().solution(self)
+    }
+
+    /// Solves the board in a single search, telling apart unique, ambiguous
+    /// and unsolvable boards. The board itself is left unchanged.
+    pub fn try_solve(&mut self) -> SolveOutcome {
+        RecursiveSearch::new(self).outcome()
+    }
+
+    /// Same as [`Tango::try_solve`], but gives up with
+    /// [`SolveOutcome::Cancelled`] soon after `cancel` is set, e.g. from
+    /// another thread.
+    pub fn solve_cancellable(&mut self, cancel: &AtomicBool) -> SolveOutcome {
+        RecursiveSearch::new(self).cancellable(cancel).outcome()
+    }
+
+    /// Same as [`Tango::try_solve`], but gives up with
+    /// [`SolveOutcome::Limited`] after trying `max_steps` tiles. Unlike a
+    /// timeout this bounds the work the same way on every machine.
+    pub fn solve_with_limit(&mut self, max_steps: usize) -> SolveOutcome {
+        RecursiveSearch::new(self).step_limited(max_steps).outcome()
+    }
+
+    /// Reveals the solution of a random empty cell. Only works on boards
+    /// with exactly one solution.
+    pub fn reveal(&self, rng: &mut impl Rng) -> Option<(Coord, TangoTile)> {
+        if self.count_solutions(2) != 1 {
+            return None;
+        }
+        let solution = self.solution()?;
+        let (coord, _) = self
+            .grid
+            .iter_coords()
+            .filter(|(_, tile)| **tile == TangoTile::Empty)
+            .choose(rng)?;
+        Some((coord, solution.get_tile(coord)?))
+    }
+
+    /// The placed tiles that together leave the board without a solution, or
+    /// `None` if it can still be solved. Clears the tiles one at a time,
+    /// keeping each cleared while the rest stays unsolvable, so no tile of
+    /// the result can be dropped. This takes a solver run per placed tile.
+    pub fn contradiction_cells(&self) -> Option<Vec<(usize, usize)>> {
+        if !self.has_no_solution() {
+            return None;
+        }
+        self.clone().clear_unneeded_tiles()
+    }
+
+    /// Like [`Tango::contradiction_cells`], but drops restrictions as well,
+    /// leaving the placed tiles and restrictions that together have no
+    /// solution, e.g. to show a designer why a hand-built puzzle is broken.
+    /// No clue of the result can be dropped, though a different and smaller
+    /// conflict may exist. Takes a solver run per placed tile and
+    /// restriction.
+    pub fn minimal_conflict(&self) -> Option<Conflict> {
+        if !self.has_no_solution() {
+            return None;
+        }
+        let mut board = self.clone();
+        let cells = board.clear_unneeded_tiles()?;
+        let mut restrictions = Vec::new();
+        for restriction in &self.restrictions {
+            let index =
+                board.restrictions.iter().position(|r| r == restriction)?;
+            let removed = board.restrictions.remove(index);
+            if !board.has_no_solution() {
+                // Needed for the contradiction, so it goes back
+                board.restrictions.insert(index, removed);
+                restrictions.push(restriction.clone());
+            }
+        }
+        Some((cells, restrictions))
+    }
+
+    fn has_no_solution(&self) -> bool {
+        self.count_solutions(1) == 0
+    }
+
+    /// Clears every placed tile the board stays unsolvable without,
+    /// returning the remaining ones.
+    fn clear_unneeded_tiles(&mut self) -> Option<Vec<(usize, usize)>> {
+        let placed: Vec<Coord> = self
+            .grid
+            .iter_coords()
+            .filter(|(_, tile)| **tile != TangoTile::Empty)
+            .map(|(coord, _)| coord)
+            .collect();
+        let mut cells = Vec::new();
+        for coord in placed {
+            let tile = self.get_tile(coord)?;
+            self.clear_tile(coord);
+            if !self.has_no_solution() {
+                // Needed for the contradiction, so it goes back
+                *self.grid.get_mut(coord.x, coord.y)? = tile;
+                cells.push((coord.x, coord.y));
+            }
+        }
+        Some(cells)
+    }
+
+    /// Every cell whose tile differs from `other`, as `(x, y, old, new)`
+    /// with `self` as the old board, in row-major order. Only the tiles are
+    /// compared, not the restrictions.
+    pub fn diff(
+        &self,
+        other: &Tango,
+    ) -> Result<Vec<(usize, usize, TangoTile, TangoTile)>, TangoError> {
+        let size = |tango: &Tango| (tango.grid.width, tango.grid.height);
+        if size(self) != size(other) {
+            return Err(TangoError::DimensionMismatch(size(self), size(other)));
+        }
+        Ok(self
+            .grid
+            .iter_coords()
+            .zip(other.grid.iter())
+            .filter(|((_, old), new)| old != new)
+            .map(|((Coord { x, y }, old), new)| (x, y, *old, *new))
+            .collect())
+    }
+
+    /// Whether both boards have the same size and tiles, e.g. a submitted
+    /// board and the computed solution. Unlike `==`, the restrictions, rules
+    /// and [`BoardKind`] don't matter.
+    pub fn same_solution(&self, other: &Tango) -> bool {
+        self.grid == other.grid
+    }
+
+    /// Correct placements still needed to finish the board, i.e. its empty
+    /// cells. `None` when the tiles placed so far don't lead to exactly one
+    /// solution, which on a unique puzzle means one of them is wrong.
+    pub fn moves_to_solution(&self) -> Option<usize> {
+        (self.count_solutions(2) == 1)
+            .then(|| self.count_total(TangoTile::Empty))
+    }
+
+    /// Quickly produces a random solved board without any restrictions, e.g.
+    /// for demos. Returns `None` for dimensions a board can't have.
+    pub fn random_solution(
+        width: usize,
+        height: usize,
+        rng: &mut impl Rng,
+    ) -> Option<Tango> {
+        let mut tango = Tango::new(width, height, vec![]).ok()?;
+        tango.grid = RecursiveSearch::new(&mut tango)
+            .randomized(rng)
+            .first_solution()?;
+        tango.kind = BoardKind::Solution;
+        Some(tango)
+    }
+
+    /// A puzzle whose only solution is `solution`, e.g. a picture drawn in
+    /// red. Keeps the restrictions of `solution` and clears its tiles in a
+    /// random order, each only if the puzzle stays unique, so no given of
+    /// the result can be dropped.
+    pub fn puzzle_for_solution(
+        solution: &Tango,
+        rng: &mut impl Rng,
+    ) -> Result<Tango, GenerationError> {
+        if !solution.is_solved() {
+            return Err(GenerationError::NotSolved);
+        }
+        let mut puzzle = solution.clone();
+        puzzle.kind = BoardKind::Puzzle;
+        let mut cells: Vec<Coord> =
+            puzzle.grid.iter_coords().map(|(coord, _)| coord).collect();
+        cells.shuffle(rng);
+        for coord in cells {
+            let tile = puzzle.get_tile(coord).unwrap_or_default();
+            puzzle.clear_tile(coord);
+            if puzzle.count_solutions(2) != 1 {
+                // Needed to pin down the solution, so it goes back
+                if let Some(cell) = puzzle.grid.get_mut(coord.x, coord.y) {
+                    *cell = tile;
+                }
+            }
+        }
+        Ok(puzzle)
+    }
+
+    /// Whether [`Tango::autofill`] solves the board completely, i.e. it can be
+    /// solved without guessing.
+    pub fn is_logic_only(&self) -> bool {
+        let mut filled = self.clone();
+        filled.autofill();
+        filled.is_solved()
+    }
+
+    /// Number of placements the solver tries and has to take back before
+    /// reaching the first solution, or `None` if there is no solution.
+    pub fn difficulty(&self) -> Option<usize> {
+        self.difficulty_with_limit(usize::MAX).flatten()
+    }
+
+    /// Same as [`Tango::difficulty`], but `None` once the search tried
+    /// `max_steps` tiles.
+    fn difficulty_with_limit(&self, max_steps: usize) -> Option<Option<usize>> {
+        let empty = self.count_total(TangoTile::Empty);
+        let mut board = self.clone();
+        let mut solver =
+            RecursiveSearch::new(&mut board).step_limited(max_steps);
+        let found = solver.solve(false) > 0;
+        if solver.limited {
+            return None;
+        }
+        Some(found.then(|| solver.steps - empty))
+    }
+
+    pub fn analyze(&self) -> PuzzleAnalysis {
+        self.analyze_with_limit(usize::MAX)
+            .expect("Searches without a step limit always finish")
+    }
+
+    /// Same as [`Tango::analyze`], but gives up with `None` once one of its
+    /// searches tried `max_steps` tiles, see [`Tango::solve_with_limit`].
+    pub fn analyze_with_limit(
+        &self,
+        max_steps: usize,
+    ) -> Option<PuzzleAnalysis> {
+        let mut board = self.clone();
+        let mut search =
+            RecursiveSearch::new(&mut board).step_limited(max_steps);
+        let solution_count_capped = search.count_capped(ANALYSIS_SOLUTION_CAP);
+        if search.limited {
+            return None;
+        }
+        let unique = solution_count_capped == 1;
+        let difficulty = if unique {
+            self.difficulty_with_limit(max_steps)?
+        } else {
+            None
+        };
+        Some(PuzzleAnalysis {
+            unique,
+            solution_count_capped,
+            difficulty,
+            satisfiable: solution_count_capped > 0,
+            metrics: self.solve_metrics_with_limit(max_steps)?,
+        })
+    }
+
+    /// Measures the solver while it counts up to two solutions. It always
+    /// branches on a forced cell first, see [`BranchOrder::Mrv`], so a board
+    /// [`Tango::autofill`] solves takes no guesses and no backtracks.
+    pub fn solve_metrics(&self) -> SolveMetrics {
+        self.solve_metrics_with_limit(usize::MAX)
+            .expect("Searches without a step limit always finish")
+    }
+
+    /// Same as [`Tango::solve_metrics`], but `None` once the search tried
+    /// `max_steps` tiles.
+    fn solve_metrics_with_limit(
+        &self,
+        max_steps: usize,
+    ) -> Option<SolveMetrics> {
+        let mut board = self.clone();
+        let mut search = RecursiveSearch::new(&mut board)
+            .ordered(BranchOrder::Mrv)
+            .step_limited(max_steps);
+        // `Instant::now` panics on `wasm32`, as browsers provide no clock
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = Instant::now();
+        search.count_capped(2);
+        if search.limited {
+            return None;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let elapsed_micros = Some(start.elapsed().as_micros() as u64);
+        #[cfg(target_arch = "wasm32")]
+        let elapsed_micros = None;
+        Some(SolveMetrics {
+            steps: search.steps,
+            guesses: search.guesses,
+            backtracks: search.backtracks,
+            elapsed_micros,
+        })
+    }
+
+    pub fn get_tile(&self, coord: impl Into<Coord>) -> Option<TangoTile> {
+        let Coord { x, y } = coord.into();
+        self.grid.get(x, y).cloned()
+    }
+
+    /// The tiles above, right of, below and left of the cell, see
+    /// [`Grid::neighbors`].
+    pub fn neighbor_tiles(
+        &self,
+        coord: impl Into<Coord>,
+    ) -> [Option<TangoTile>; 4] {
+        let Coord { x, y } = coord.into();
+        self.grid.neighbors(x, y).map(|tile| tile.copied())
+    }
+
+    /// Number of `tile`s in row `y`, zero if it is out of bounds.
+    pub fn count_in_row(&self, y: usize, tile: TangoTile) -> usize {
+        (0..self.grid.width)
+            .filter(|&x| self.get_tile((x, y)) == Some(tile))
+            .count()
+    }
+
+    /// Number of `tile`s in column `x`, zero if it is out of bounds.
+    pub fn count_in_column(&self, x: usize, tile: TangoTile) -> usize {
+        (0..self.grid.height)
+            .filter(|&y| self.get_tile((x, y)) == Some(tile))
+            .count()
+    }
+
+    /// The `(reds, blues)` each row and each column still needs to be
+    /// balanced, e.g. for a side panel. A line already holding too many of a
+    /// color needs none of it.
+    pub fn line_remaining(&self) -> (LineRemaining, LineRemaining) {
+        let needed = |half: usize, reds: usize, blues: usize| {
+            (half.saturating_sub(reds), half.saturating_sub(blues))
+        };
+        let rows = (0..self.grid.height)
+            .map(|y| {
+                needed(
+                    self.grid.width / 2,
+                    self.count_in_row(y, TangoTile::Red),
+                    self.count_in_row(y, TangoTile::Blue),
+                )
+            })
+            .collect();
+        let columns = (0..self.grid.width)
+            .map(|x| {
+                needed(
+                    self.grid.height / 2,
+                    self.count_in_column(x, TangoTile::Red),
+                    self.count_in_column(x, TangoTile::Blue),
+                )
+            })
+            .collect();
+        (rows, columns)
+    }
+
+    /// Whether the empty cells of row (`is_row`) or column `index` can still
+    /// be filled with as many reds as blues without a run longer than the
+    /// rules allow. Catches lines that can no longer be completed even
+    /// though they break no rule yet, like `RR...R`. Ignores restrictions
+    /// and the crossing lines, and is `false` if `index` is out of bounds.
+    pub fn line_balanceable(&self, is_row: bool, index: usize) -> bool {
+        let (len, max_run) = if is_row {
+            (self.grid.width, self.rules.row_max_run)
+        } else {
+            (self.grid.height, self.rules.col_max_run)
+        };
+        let tiles: Option<Vec<TangoTile>> = (0..len)
+            .map(|i| {
+                self.get_tile(if is_row { (i, index) } else { (index, i) })
+            })
+            .collect();
+        let Some(tiles) = tiles else {
+            return false;
+        };
+        let half = len / 2;
+        // Every `(reds, last tile, run length)` the filled prefix can end in
+        let mut states = BTreeSet::from([(0, TangoTile::Empty, 0)]);
+        for (i, tile) in tiles.into_iter().enumerate() {
+            let options: &[TangoTile] = match tile {
+                TangoTile::Empty => &[TangoTile::Red, TangoTile::Blue],
+                _ => &[tile],
+            };
+            let mut next = BTreeSet::new();
+            for &(reds, last, run) in &states {
+                for &tile in options {
+                    let run = if tile == last { run + 1 } else { 1 };
+                    let reds = reds + usize::from(tile == TangoTile::Red);
+                    let blues = i + 1 - reds;
+                    if run <= max_run && reds <= half && blues <= half {
+                        next.insert((reds, tile, run));
+                    }
+                }
+            }
+            states = next;
+        }
+        !states.is_empty()
+    }
+
+    /// Whether every cell of row (`is_row`) or column `index` is filled
+    /// without breaking the balance or run rules, e.g. to animate the line.
+    /// Restrictions and diagonals are not checked, and it is `false` if
+    /// `index` is out of bounds.
+    pub fn is_line_complete(&self, is_row: bool, index: usize) -> bool {
+        let (len, valid) = if is_row {
+            (self.grid.width, self.is_valid_row(index))
+        } else {
+            (self.grid.height, self.is_valid_column(index))
+        };
+        valid
+            && (0..len).all(|i| {
+                let cell = if is_row { (i, index) } else { (index, i) };
+                self.get_tile(cell).is_some_and(|t| t != TangoTile::Empty)
+            })
+    }
+
+    /// Number of `tile`s on the whole board.
+    pub fn count_total(&self, tile: TangoTile) -> usize {
+        self.grid.count(|t| *t == tile)
+    }
+
+    fn is_valid_row(&self, y: usize) -> bool {
+        if y >= self.grid.height {
+            return false;
+        }
+        let mut last_tile = TangoTile::Empty;
+        let mut consecuteive_same_count = 0;
+        for x in 0..self.grid.width {
+            if let Some(tile) = self.get_tile((x, y)) {
                 if tile != TangoTile::Empty && tile == last_tile {
                     consecuteive_same_count += 1;
-                    if consecuteive_same_count > 1 {
-                        return false; // More than one consecutive same tile
+                    if consecuteive_same_count >= self.rules.row_max_run {
+                        return false; // Run longer than the rules allow
                     }
                 } else {
                     consecuteive_same_count = 0; // Reset count for different tile
@@ -254,182 +1449,1069 @@ impl Tango {
                 last_tile = tile;
             }
         }
-        red_count <= self.grid.height / 2 && blue_count <= self.grid.height / 2
+        let half = self.grid.width / 2;
+        self.count_in_row(y, TangoTile::Red) <= half
+            && self.count_in_row(y, TangoTile::Blue) <= half
     }
-    fn check_restrictions(&self) -> bool {
-        for restriction in &self.restrictions {
-            match restriction {
-                TangoRestriction::Same((x1, y1), (x2, y2)) => {
-                    if let (Some(tile1), Some(tile2)) =
-                        (self.get_tile(*x1, *y1), self.get_tile(*x2, *y2))
-                    {
-                        if tile1 == TangoTile::Empty
-                            || tile2 == TangoTile::Empty
-                        {
-                            continue; // Empty tiles can be ignored
-                        }
-                        if tile1 != tile2 {
-                            return false;
-                        }
+    fn is_valid_column(&self, x: usize) -> bool {
+        if x >= self.grid.width {
+            return false;
+        }
+        let mut last_tile = TangoTile::Empty;
+        let mut consecuteive_same_count = 0;
+        for y in 0..self.grid.height {
+            if let Some(tile) = self.get_tile((x, y)) {
+                if tile != TangoTile::Empty && tile == last_tile {
+                    consecuteive_same_count += 1;
+                    if consecuteive_same_count >= self.rules.col_max_run {
+                        return false; // Run longer than the rules allow
                     }
+                } else {
+                    consecuteive_same_count = 0; // Reset count for different tile
                 }
-                TangoRestriction::Different((x1, y1), (x2, y2)) => {
-                    if let (Some(tile1), Some(tile2)) =
-                        (self.get_tile(*x1, *y1), self.get_tile(*x2, *y2))
+                last_tile = tile;
+            }
+        }
+        let half = self.grid.height / 2;
+        self.count_in_column(x, TangoTile::Red) <= half
+            && self.count_in_column(x, TangoTile::Blue) <= half
+    }
+    /// A valid board with every cell filled.
+    pub fn is_solved(&self) -> bool {
+        self.grid.iter().all(|tile| *tile != TangoTile::Empty)
+            && self.is_valid()
+    }
+
+    /// Validates the whole board, including the optional rule variants.
+    pub fn is_valid(&self) -> bool {
+        (0..self.grid.height).all(|y| self.is_valid_row(y))
+            && (0..self.grid.width).all(|x| self.is_valid_column(x))
+            && self.check_restrictions()
+            && (!self.rules.check_diagonals || self.is_valid_diagonals())
+    }
+
+    fn is_valid_diagonals(&self) -> bool {
+        for y in 0..self.grid.height.saturating_sub(2) {
+            for x in 0..self.grid.width.saturating_sub(2) {
+                let diagonal = [(x, y), (x + 1, y + 1), (x + 2, y + 2)];
+                let anti_diagonal = [(x + 2, y), (x + 1, y + 1), (x, y + 2)];
+                for cells in [diagonal, anti_diagonal] {
+                    let tiles = cells.map(|(x, y)| self.get_tile((x, y)));
+                    if tiles[0] != Some(TangoTile::Empty)
+                        && tiles[0] == tiles[1]
+                        && tiles[1] == tiles[2]
                     {
-                        if tile1 == TangoTile::Empty
-                            || tile2 == TangoTile::Empty
-                        {
-                            continue; // Empty tiles can be ignored
-                        }
-                        if tile1 == tile2 {
-                            return false;
-                        }
+                        return false;
                     }
                 }
             }
         }
         true
     }
+
+    fn check_restrictions(&self) -> bool {
+        self.restrictions.iter().all(|restriction| {
+            self.restriction_status(restriction) != RestrictionStatus::Violated
+        })
+    }
+
+    /// Checks a single restriction against the current tiles. Cells outside
+    /// of the board count as empty.
+    pub fn restriction_status(
+        &self,
+        restriction: &TangoRestriction,
+    ) -> RestrictionStatus {
+        let (a, b) = restriction.cells();
+        let Some((&tile1, &tile2)) = self.grid.get_pair(a.into(), b.into())
+        else {
+            return RestrictionStatus::Pending;
+        };
+        if tile1 == TangoTile::Empty || tile2 == TangoTile::Empty {
+            return RestrictionStatus::Pending;
+        }
+        let holds = match restriction {
+            TangoRestriction::Same(_, _) => tile1 == tile2,
+            TangoRestriction::Different(_, _) => tile1 != tile2,
+        };
+        if holds {
+            RestrictionStatus::Satisfied
+        } else {
+            RestrictionStatus::Violated
+        }
+    }
+
+    /// Restrictions are read-only outside of the crate, new ones have to go
+    /// through [`Tango::add_restriction`]:
+    ///
+    /// ```compile_fail
+    /// # use ligames::{TangoGenerator, TangoRestriction};
+    /// let mut tango = TangoGenerator::new(4, 4).unwrap().generate();
+    /// tango.restrictions.push(TangoRestriction::Same((0, 0).into(), (1, 0).into()));
+    /// ```
+    pub fn restrictions(&self) -> &[TangoRestriction] {
+        &self.restrictions
+    }
+
+    /// The restrictions split into `Same`s and `Different`s, keeping their
+    /// order within each kind.
+    pub fn restrictions_by_kind(&self) -> RestrictionsByKind {
+        self.restrictions.iter().collect()
+    }
+
+    /// Every pair of adjacent cells a restriction can connect, see
+    /// [`Grid::edges`].
+    pub fn edges(&self) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+        self.grid.edges()
+    }
+
+    /// Adds a restriction between two orthogonally adjacent cells that are
+    /// not restricted yet.
+    pub fn add_restriction(
+        &mut self,
+        restriction: TangoRestriction,
+    ) -> Result<(), TangoError> {
+        let (a, b) = restriction.cells();
+        for cell in [a, b] {
+            if self.cell_index(cell).is_none() {
+                return Err(TangoError::OutOfBounds(cell));
+            }
+        }
+        if a.x.abs_diff(b.x) + a.y.abs_diff(b.y) != 1 {
+            return Err(TangoError::NotAdjacent(a, b));
+        }
+        if let Some(existing) = self.get_restriction(a, b) {
+            // The existing one may list the cells the other way around
+            let same_kind = matches!(
+                (existing, &restriction),
+                (TangoRestriction::Same(..), TangoRestriction::Same(..))
+                    | (
+                        TangoRestriction::Different(..),
+                        TangoRestriction::Different(..)
+                    )
+            );
+            return Err(if same_kind {
+                TangoError::DuplicateRestriction(a, b)
+            } else {
+                TangoError::ContradictingRestriction(a, b)
+            });
+        }
+        self.restrictions.push(restriction);
+        Ok(())
+    }
+
+    /// Checks whether [`Tango::add_restriction`] would accept the
+    /// restriction and whether it is consistent with the board, i.e. holds
+    /// for the placed tiles and doesn't contradict the other restrictions,
+    /// e.g. before an editor adds it. Leaves the board unchanged.
+    pub fn can_add_restriction(
+        &self,
+        restriction: &TangoRestriction,
+    ) -> Result<(), TangoError> {
+        let mut board = self.clone();
+        board.add_restriction(restriction.clone())?;
+        let (a, b) = restriction.cells();
+        if board.restriction_status(restriction) == RestrictionStatus::Violated
+        {
+            return Err(TangoError::BrokenRestriction(a, b));
+        }
+        if self.restrictions_satisfiable() && !board.restrictions_satisfiable()
+        {
+            return Err(TangoError::ContradictingCycle(a, b));
+        }
+        Ok(())
+    }
+
+    /// Every restriction with `(x, y)` as one of its cells, in stored order.
+    pub fn restrictions_for_cell(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> Vec<&TangoRestriction> {
+        let cell = Coord::new(x, y);
+        self.restrictions
+            .iter()
+            .filter(|restriction| {
+                let (a, b) = restriction.cells();
+                a == cell || b == cell
+            })
+            .collect()
+    }
+
     fn get_restriction(
         &self,
-        a: (usize, usize),
-        b: (usize, usize),
+        a: impl Into<Coord>,
+        b: impl Into<Coord>,
     ) -> Option<&TangoRestriction> {
-        self.restrictions.iter().find(|r| match r {
-            TangoRestriction::Same((x1, y1), (x2, y2)) => {
-                (x1 == &a.0 && y1 == &a.1 && x2 == &b.0 && y2 == &b.1)
-                    || (x2 == &a.0 && y2 == &a.1 && x1 == &b.0 && y1 == &b.1)
+        let (a, b) = (a.into(), b.into());
+        self.restrictions.iter().find(|r| {
+            let cells = r.cells();
+            cells == (a, b) || cells == (b, a)
+        })
+    }
+
+    fn restriction_union_find(&self) -> ParityUnionFind {
+        let mut union_find =
+            ParityUnionFind::new(self.grid.width * self.grid.height);
+        for restriction in &self.restrictions {
+            let (a, b, opposite) = match restriction {
+                TangoRestriction::Same(a, b) => (a, b, false),
+                TangoRestriction::Different(a, b) => (a, b, true),
+            };
+            if let (Some(a), Some(b)) =
+                (self.cell_index(*a), self.cell_index(*b))
+            {
+                union_find.union(a, b, opposite);
             }
-            TangoRestriction::Different((x1, y1), (x2, y2)) => {
-                (x1 == &a.0 && y1 == &a.1 && x2 == &b.0 && y2 == &b.1)
-                    || (x2 == &a.0 && y2 == &a.1 && x1 == &b.0 && y1 == &b.1)
+        }
+        union_find
+    }
+
+    fn cell_index(&self, Coord { x, y }: Coord) -> Option<usize> {
+        self.grid.index(x, y)
+    }
+
+    /// Checks that the `Same`/`Different` relations alone can be satisfied,
+    /// i.e. that no cycle of restrictions contains an odd number of
+    /// `Different`s. Line rules are not taken into account.
+    pub fn restrictions_satisfiable(&self) -> bool {
+        self.restriction_union_find().consistent
+    }
+
+    /// Groups the cells touched by restrictions into connected components.
+    /// Cells within a component are listed in row-major order and the
+    /// components are ordered by their first cell.
+    pub fn restriction_components(&self) -> Vec<Vec<Coord>> {
+        let mut union_find = self.restriction_union_find();
+        let mut components: HashMap<usize, Vec<Coord>> = HashMap::new();
+        let mut roots = Vec::new();
+        for y in 0..self.grid.height {
+            for x in 0..self.grid.width {
+                let index = y * self.grid.width + x;
+                let (root, _) = union_find.find(index);
+                if root == index && union_find.size[root] == 1 {
+                    continue; // Cell without any restriction
+                }
+                components
+                    .entry(root)
+                    .or_insert_with(|| {
+                        roots.push(root);
+                        Vec::new()
+                    })
+                    .push(Coord::new(x, y));
             }
-        })
+        }
+        roots
+            .into_iter()
+            .filter_map(|root| components.remove(&root))
+            .collect()
+    }
+
+    /// The restrictions as a graph over all cells, together with its
+    /// connected components.
+    pub fn restriction_graph(&self) -> RestrictionGraph {
+        RestrictionGraph {
+            nodes: self.grid.iter_coords().map(|(coord, _)| coord).collect(),
+            edges: self.restrictions.clone(),
+            components: self.restriction_components(),
+        }
+    }
+
+    /// Returns `Some(true)` if the restriction chains force `a` and `b` to
+    /// share a color, `Some(false)` if they force opposite colors and `None`
+    /// if the cells are not connected.
+    pub fn must_match(
+        &self,
+        a: impl Into<Coord>,
+        b: impl Into<Coord>,
+    ) -> Option<bool> {
+        let (a, b) = (self.cell_index(a.into())?, self.cell_index(b.into())?);
+        let mut union_find = self.restriction_union_find();
+        let (root_a, parity_a) = union_find.find(a);
+        let (root_b, parity_b) = union_find.find(b);
+        (root_a == root_b).then_some(parity_a == parity_b)
+    }
+}
+
+/// Graph view of the restrictions, see [`Tango::restriction_graph`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestrictionGraph {
+    /// Every cell of the board in row-major order.
+    pub nodes: Vec<Coord>,
+    pub edges: Vec<TangoRestriction>,
+    /// Cells connected by restrictions, as in
+    /// [`Tango::restriction_components`].
+    pub components: Vec<Vec<Coord>>,
+}
+
+/// 64-bit FNV-1a, used where a hash has to be stable across platforms and
+/// Rust versions.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Union-find over cell indices that also tracks, for every cell, whether it
+/// has the opposite color of its component root.
+struct ParityUnionFind {
+    parent: Vec<usize>,
+    parity: Vec<bool>,
+    size: Vec<usize>,
+    /// Cleared once a union contradicts the relations known so far.
+    consistent: bool,
+}
+
+impl ParityUnionFind {
+    fn new(len: usize) -> Self {
+        ParityUnionFind {
+            parent: (0..len).collect(),
+            parity: vec![false; len],
+            size: vec![1; len],
+            consistent: true,
+        }
+    }
+
+    fn find(&mut self, index: usize) -> (usize, bool) {
+        let parent = self.parent[index];
+        if parent == index {
+            return (index, false);
+        }
+        let (root, parent_parity) = self.find(parent);
+        self.parent[index] = root;
+        self.parity[index] ^= parent_parity;
+        (root, self.parity[index])
+    }
+
+    /// Joins the components of `a` and `b`, `opposite` meaning the cells must
+    /// differ. Returns `false` if this contradicts the known relations.
+    fn union(&mut self, a: usize, b: usize, opposite: bool) -> bool {
+        let (mut root_a, parity_a) = self.find(a);
+        let (mut root_b, parity_b) = self.find(b);
+        if root_a == root_b {
+            let agrees = (parity_a ^ parity_b) == opposite;
+            self.consistent &= agrees;
+            return agrees;
+        }
+        if self.size[root_a] < self.size[root_b] {
+            std::mem::swap(&mut root_a, &mut root_b);
+        }
+        self.parent[root_b] = root_a;
+        self.parity[root_b] = parity_a ^ parity_b ^ opposite;
+        self.size[root_a] += self.size[root_b];
+        true
     }
 }
 
-struct RecursiveTangoSolver {
-    tango: Tango,
+/// Backtracking solver working directly on a borrowed board, which is
+/// restored to its original state once the search returns.
+struct RecursiveSearch<'a> {
+    tango: &'a mut Tango,
+    limit: usize,
+    steps: usize,
+    /// Cells where both colors fit and were tried.
+    guesses: usize,
+    /// Placements taken back without leading to a solution.
+    backtracks: usize,
+    /// Picks the order of the colors tried in each cell at random.
+    rng: Option<StdRng>,
+    /// Tiles of the first solution reached during the search.
+    solution: Option<Grid<TangoTile>>,
+    /// Tiles of every solution reached, only kept when collecting.
+    solutions: Option<Vec<Grid<TangoTile>>>,
+    /// Aborts the search once set.
+    cancel: Option<&'a AtomicBool>,
+    cancelled: bool,
+    /// Aborts the search once this many tiles were tried.
+    max_steps: Option<usize>,
+    limited: bool,
+    /// Fills forced tiles after every placement, which prunes large boards
+    /// but costs a scan of the board per placement.
+    propagate: bool,
+    /// Which empty cell is branched on next.
+    branch_order: BranchOrder,
 }
 
-impl RecursiveTangoSolver {
-    fn new(tango: Tango) -> Self {
-        RecursiveTangoSolver { tango }
+impl<'a> RecursiveSearch<'a> {
+    fn new(tango: &'a mut Tango) -> Self {
+        RecursiveSearch {
+            tango,
+            limit: usize::MAX,
+            steps: 0,
+            guesses: 0,
+            backtracks: 0,
+            rng: None,
+            solution: None,
+            solutions: None,
+            cancel: None,
+            cancelled: false,
+            max_steps: None,
+            limited: false,
+            propagate: false,
+            branch_order: BranchOrder::RowMajor,
+        }
+    }
+
+    fn propagating(mut self) -> Self {
+        self.propagate = true;
+        self
+    }
+
+    fn ordered(mut self, branch_order: BranchOrder) -> Self {
+        self.branch_order = branch_order;
+        self
+    }
+
+    fn collecting(mut self) -> Self {
+        self.solutions = Some(Vec::new());
+        self
+    }
+
+    fn cancellable(mut self, cancel: &'a AtomicBool) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn step_limited(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    fn randomized(mut self, rng: &mut impl Rng) -> Self {
+        self.rng = Some(StdRng::from_rng(rng));
+        self
     }
 
     fn solve(&mut self, counter_mode: bool) -> usize {
-        self.solve_recursive(counter_mode, 0)
-    }
-
-    fn solve_recursive(&mut self, counter_mode: bool, mut acc: usize) -> usize {
-        for y in 0..self.tango.grid.height {
-            for x in 0..self.tango.grid.width {
-                if let Some(tile) = self.tango.get_tile(x, y) {
-                    if tile == TangoTile::Empty {
-                        for &new_tile in &[TangoTile::Red, TangoTile::Blue] {
-                            if self.tango.set_tile(x, y, new_tile) {
-                                let result =
-                                    self.solve_recursive(counter_mode, acc);
-                                if result > 0 {
-                                    if !counter_mode {
-                                        return result; // Return the count
-                                    } else {
-                                        acc = result;
-                                    }
-                                }
-                            }
-                            // Reset the tile if it doesn't lead to a solution
-                            self.tango.set_tile(x, y, TangoTile::Empty);
-                        }
-                        return acc; // No valid tile found
+        self.limit = if counter_mode { usize::MAX } else { 1 };
+        self.search_root()
+    }
+
+    fn first_solution(&mut self) -> Option<Grid<TangoTile>> {
+        self.solve(false);
+        self.solution.take()
+    }
+
+    /// Counts solutions, stopping as soon as `cap` of them were found.
+    fn count_capped(&mut self, cap: usize) -> usize {
+        self.limit = cap;
+        self.search_root()
+    }
+
+    /// Every search starts here. Placed tiles are taken as given and never
+    /// checked again, so a board already breaking the rules has no solution
+    /// even when it's full.
+    fn search_root(&mut self) -> usize {
+        if !self.tango.is_valid() {
+            return 0;
+        }
+        self.solve_recursive(0)
+    }
+
+    fn outcome(&mut self) -> SolveOutcome {
+        let count = self.count_capped(2);
+        if self.cancelled {
+            return SolveOutcome::Cancelled;
+        }
+        if self.limited {
+            return SolveOutcome::Limited;
+        }
+        match (count, self.solution.take()) {
+            (0, _) | (_, None) => SolveOutcome::None,
+            (1, Some(grid)) => SolveOutcome::Unique(Tango {
+                grid,
+                kind: BoardKind::Solution,
+                ..self.tango.clone()
+            }),
+            (count_capped, Some(_)) => SolveOutcome::Multiple { count_capped },
+        }
+    }
+
+    /// When propagating, places every tile forced by the one just placed at
+    /// `from`, following the rows, columns and restrictions of the changed
+    /// cells. Returns the filled cells so they can be cleared again, and
+    /// whether the board can still be completed.
+    fn fill_forced(&mut self, from: Coord) -> (Vec<Coord>, bool) {
+        let mut filled = Vec::new();
+        if !self.propagate {
+            return (filled, true);
+        }
+        let (width, height) = (self.tango.grid.width, self.tango.grid.height);
+        let mut changed = vec![from];
+        while let Some(Coord { x, y }) = changed.pop() {
+            let row = (0..width).map(|x| Coord::new(x, y));
+            let column = (0..height).map(|y| Coord::new(x, y));
+            let partners =
+                self.tango.restrictions_for_cell(x, y).into_iter().map(|r| {
+                    let (a, b) = r.cells();
+                    if a == Coord::new(x, y) {
+                        b
+                    } else {
+                        a
                     }
+                });
+            let neighbors: Vec<_> = row.chain(column).chain(partners).collect();
+            for cell in neighbors {
+                if self.tango.get_tile(cell) != Some(TangoTile::Empty) {
+                    continue;
                 }
+                let red = self.tango.fits(cell.x, cell.y, TangoTile::Red);
+                let blue = self.tango.fits(cell.x, cell.y, TangoTile::Blue);
+                let tile = match (red, blue) {
+                    (true, true) => continue,
+                    (true, false) => TangoTile::Red,
+                    (false, true) => TangoTile::Blue,
+                    (false, false) => return (filled, false),
+                };
+                let placed = self.tango.set_tile(cell, tile);
+                debug_assert!(placed, "A forced tile always fits");
+                filled.push(cell);
+                changed.push(cell);
+            }
+        }
+        (filled, true)
+    }
+
+    /// The empty cell to branch on next, or `None` once the board is full.
+    fn next_cell(&mut self) -> Option<Coord> {
+        let first_empty = || {
+            self.tango
+                .grid
+                .iter_coords()
+                .find(|(_, tile)| **tile == TangoTile::Empty)
+                .map(|(cell, _)| cell)
+        };
+        match self.branch_order {
+            BranchOrder::RowMajor => first_empty(),
+            BranchOrder::Mrv => {
+                let empty: Vec<Coord> = self
+                    .tango
+                    .grid
+                    .iter_coords()
+                    .filter(|(_, tile)| **tile == TangoTile::Empty)
+                    .map(|(cell, _)| cell)
+                    .collect();
+                // A cell fitting at most one color can't be beaten
+                let constrained = empty.iter().copied().find(|cell| {
+                    !self.tango.fits(cell.x, cell.y, TangoTile::Red)
+                        || !self.tango.fits(cell.x, cell.y, TangoTile::Blue)
+                });
+                constrained.or(empty.first().copied())
+            }
+            BranchOrder::RestrictionFirst => {
+                let tiles = &self.tango.grid;
+                let touching = self.tango.restrictions.iter().find_map(|r| {
+                    let (a, b) = r.cells();
+                    match (tiles.get(a.x, a.y)?, tiles.get(b.x, b.y)?) {
+                        (TangoTile::Empty, TangoTile::Empty) => None,
+                        (TangoTile::Empty, _) => Some(a),
+                        (_, TangoTile::Empty) => Some(b),
+                        _ => None,
+                    }
+                });
+                touching.or_else(first_empty)
             }
         }
+    }
+
+    fn solve_recursive(&mut self, mut acc: usize) -> usize {
+        if let Some(cancel) = self.cancel {
+            if self.cancelled || cancel.load(atomic::Ordering::Relaxed) {
+                self.cancelled = true;
+                return self.limit; // Unwinds like a reached limit
+            }
+        }
+        if let Some(Coord { x, y }) = self.next_cell() {
+            let mut placed = 0;
+            let mut order = [TangoTile::Red, TangoTile::Blue];
+            if let Some(rng) = &mut self.rng {
+                if rng.random_bool(0.5) {
+                    order.reverse();
+                }
+            }
+            for new_tile in order {
+                if self.max_steps.is_some_and(|max| self.steps >= max) {
+                    self.limited = true;
+                    return self.limit; // Unwinds like cancelling
+                }
+                self.steps += 1;
+                if self.tango.set_tile((x, y), new_tile) {
+                    placed += 1;
+                    if placed == 2 {
+                        self.guesses += 1; // Both colors fit
+                    }
+                    let (forced, consistent) =
+                        self.fill_forced(Coord::new(x, y));
+                    let result = if consistent {
+                        self.solve_recursive(acc)
+                    } else {
+                        acc // A cell fits neither color
+                    };
+                    // Restore the board before going back up
+                    for coord in forced {
+                        self.tango.clear_tile(coord);
+                    }
+                    self.tango.clear_tile((x, y));
+                    if result >= self.limit {
+                        return result; // Return the count
+                    }
+                    if result == acc {
+                        self.backtracks += 1;
+                    }
+                    acc = result;
+                }
+            }
+            return acc; // No valid tile found
+        }
+        if self.tango.rules.check_diagonals && !self.tango.is_valid_diagonals()
+        {
+            return acc; // Diagonals are only checked on complete boards
+        }
         // println!("Reached a solution state\n{}", self.tango);
+        if self.solution.is_none() {
+            self.solution = Some(self.tango.grid.clone());
+        }
+        if let Some(solutions) = &mut self.solutions {
+            solutions.push(self.tango.grid.clone());
+        }
         acc + 1
     }
 }
 
+/// The cells of the placed tiles and the restrictions of
+/// [`Tango::minimal_conflict`].
+pub type Conflict = (Vec<(usize, usize)>, Vec<TangoRestriction>);
+
+/// Reds and blues still needed by each row or column, see
+/// [`Tango::line_remaining`].
+pub type LineRemaining = Vec<(usize, usize)>;
+
+/// Result of [`Tango::try_solve`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SolveOutcome {
+    /// Exactly one solution, returned as the solved board.
+    Unique(Tango),
+    /// More than one solution, counted up to `count_capped`.
+    Multiple { count_capped: usize },
+    /// The board has no solution.
+    None,
+    /// The search was cancelled before finishing.
+    Cancelled,
+    /// The search tried more tiles than allowed before finishing.
+    Limited,
+}
+
+/// Summary of [`Tango::apply_moves`].
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq,
+)]
+pub struct MoveReplay {
+    /// Index of the move after which the board was first solved.
+    pub solved_at: Option<usize>,
+    /// Index of the move that was rejected, ending the replay.
+    pub illegal_move: Option<usize>,
+}
+
+/// Maximum number of solutions counted when analyzing a puzzle.
+pub const ANALYSIS_SOLUTION_CAP: usize = 3;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PuzzleAnalysis {
+    pub unique: bool,
+    pub solution_count_capped: usize,
+    /// Search effort needed to solve the puzzle, only reported when unique.
+    pub difficulty: Option<usize>,
+    pub satisfiable: bool,
+    pub metrics: SolveMetrics,
+}
+
+/// Effort of the solver on a board, see [`Tango::solve_metrics`].
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq,
+)]
+pub struct SolveMetrics {
+    /// Tiles tried, including those breaking a rule.
+    pub steps: usize,
+    /// Cells where both colors fit, so both had to be tried.
+    pub guesses: usize,
+    /// Placements taken back because they led to no solution.
+    pub backtracks: usize,
+    /// Wall-clock time of the search, `None` on `wasm32`.
+    pub elapsed_micros: Option<u64>,
+}
+
+/// Coarse buckets over [`Tango::difficulty`], each holding about a third of
+/// the generated unique 6x6 puzzles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DifficultyTier {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl DifficultyTier {
+    /// The tier of the score with the default [`TierThresholds`].
+    pub fn from_score(score: usize) -> Self {
+        TierThresholds::default().tier(score)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    /// Exact number of pre-filled givens, taken from a solution of the
+    /// generated restrictions. `None` fills each cell with a 10% chance.
+    pub givens: Option<usize>,
+    /// Chance of a generated restriction being `Same` rather than
+    /// `Different`, clamped to `0.0..=1.0`. `1.0` gives only `Same`s and
+    /// `0.0` only `Different`s.
+    pub same_ratio: f64,
+    /// Regenerates the restrictions until both `Same` and `Different` are
+    /// among them, for variety. Needs a `same_ratio` strictly between `0.0`
+    /// and `1.0`.
+    pub require_both_restriction_kinds: bool,
+    /// Places the givens in pairs that map onto each other when the board is
+    /// turned by 180°, both taken from a solution. An odd number of
+    /// [`GeneratorConfig::givens`] is rounded up to the next pair, and
+    /// without a number each pair is filled with a 10% chance.
+    pub symmetric_givens: bool,
+    /// Most restrictions a board gets, as boards with a restriction on
+    /// nearly every edge are cluttered and mostly trivial. `None` allows a
+    /// quarter of the number of cells.
+    pub max_restrictions: Option<usize>,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        GeneratorConfig {
+            givens: None,
+            same_ratio: 0.5,
+            require_both_restriction_kinds: false,
+            symmetric_givens: false,
+            max_restrictions: None,
+        }
+    }
+}
+
+/// Lowest [`Tango::difficulty`] scores of the medium and hard
+/// [`DifficultyTier`]s. The defaults were measured on unique 6x6 puzzles;
+/// [`TangoGenerator::calibrate_tiers`] measures them again, e.g. once the
+/// generator changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierThresholds {
+    pub medium: usize,
+    pub hard: usize,
+}
+
+impl Default for TierThresholds {
+    fn default() -> Self {
+        TierThresholds {
+            medium: 100,
+            hard: 500,
+        }
+    }
+}
+
+impl TierThresholds {
+    pub fn tier(&self, score: usize) -> DifficultyTier {
+        if score >= self.hard {
+            DifficultyTier::Hard
+        } else if score >= self.medium {
+            DifficultyTier::Medium
+        } else {
+            DifficultyTier::Easy
+        }
+    }
+
+    /// Terciles of the scores, putting about a third of them into each
+    /// tier. Equal scores always share a tier, so the split is uneven when
+    /// many are equal. `None` without any scores.
+    pub fn from_scores(mut scores: Vec<usize>) -> Option<Self> {
+        if scores.is_empty() {
+            return None;
+        }
+        scores.sort_unstable();
+        let len = scores.len();
+        Some(TierThresholds {
+            medium: scores[len / 3],
+            hard: scores[len * 2 / 3],
+        })
+    }
+}
+
 pub struct TangoGenerator {
     width: usize,
     height: usize,
-    neighbor_pairs: Vec<((usize, usize), (usize, usize))>,
+    config: GeneratorConfig,
+    /// Used to check generated boards for uniqueness.
+    solver: Arc<dyn TangoSolver + Send + Sync>,
+    /// Makes every `generate*` call return the same board.
+    seed: Option<u64>,
+    /// Used by [`TangoGenerator::generate_with_tier`].
+    thresholds: TierThresholds,
 }
 
 impl TangoGenerator {
-    pub fn new(width: usize, height: usize) -> Self {
-        TangoGenerator {
+    /// Fails for dimensions no board can have, see
+    /// [`Tango::check_dimensions`].
+    pub fn new(width: usize, height: usize) -> Result<Self, GenerationError> {
+        Tango::check_dimensions(width, height)?;
+        Ok(TangoGenerator {
             width,
             height,
-            neighbor_pairs: itertools::iproduct!(0..width - 1, 0..height)
-                .zip(itertools::iproduct!(1..width, 0..height))
-                .chain(
-                    itertools::iproduct!(0..width, 0..height - 1)
-                        .zip(itertools::iproduct!(0..width, 1..height)),
-                )
-                .collect(),
+            config: GeneratorConfig::default(),
+            solver: Arc::new(RecursiveTangoSolver::default()),
+            seed: None,
+            thresholds: TierThresholds::default(),
+        })
+    }
+
+    /// Fails for settings boards can't be generated with.
+    pub fn with_config(
+        mut self,
+        config: GeneratorConfig,
+    ) -> Result<Self, GenerationError> {
+        if config.same_ratio.is_nan() {
+            return Err(GenerationError::SameRatio(config.same_ratio));
+        }
+        if config.require_both_restriction_kinds
+            && !(config.same_ratio > 0.0 && config.same_ratio < 1.0)
+        {
+            return Err(GenerationError::RestrictionKinds(config.same_ratio));
+        }
+        self.config = config;
+        Ok(self)
+    }
+
+    pub fn with_solver(
+        mut self,
+        solver: Arc<dyn TangoSolver + Send + Sync>,
+    ) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    pub fn with_tier_thresholds(mut self, thresholds: TierThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Seeds the generator, so the same seed and settings always generate
+    /// the same board.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
         }
     }
 
     pub fn generate(&self) -> Tango {
-        // Placeholder for actual generation logic
-        let mut tango = Tango::new(self.width, self.height, vec![])
-            .expect("Failed to create Tango");
+        self.generate_from(&mut self.rng())
+    }
 
-        let mut rng = &mut rand::rng();
+    fn generate_from(&self, rng: &mut impl Rng) -> Tango {
+        let givens = self.config.givens;
+        if givens.is_none() && !self.config.symmetric_givens {
+            let mut tango = self.generate_restrictions(rng);
+            // Randomly fill the grid with tiles
+            for y in 0..tango.grid.height {
+                for x in 0..tango.grid.width {
+                    if rng.random_bool(0.1) {
+                        let mut colors = [TangoTile::Red, TangoTile::Blue];
+                        if rng.random_bool(0.5) {
+                            colors.reverse();
+                        }
+                        // Falls back to the other color if the first breaks
+                        // a rule, leaving the cell empty if neither fits
+                        let _placed = colors
+                            .into_iter()
+                            .any(|tile| tango.set_tile((x, y), tile));
+                    }
+                }
+            }
+            return tango;
+        }
 
-        let to_take = rng.random_range(0..=self.neighbor_pairs.len());
-        for (a, b) in self
-            .neighbor_pairs
-            .iter()
-            .cloned()
-            .choose_multiple(&mut rng, to_take)
-        {
-            if random_bool(0.5) {
-                tango.restrictions.push(TangoRestriction::Same(a, b));
+        loop {
+            let mut tango = self.generate_restrictions(rng);
+            let Some(solution) = RecursiveSearch::new(&mut tango)
+                .randomized(rng)
+                .first_solution()
+            else {
+                continue; // Restrictions without any solution
+            };
+            let cells = self.width * self.height;
+            let given_cells: Vec<usize> = if self.config.symmetric_givens {
+                // Turning the board maps row-major index `i` to
+                // `cells - 1 - i`, and with even sides no cell to itself
+                let pairs = match givens {
+                    Some(givens) => (0..cells / 2).choose_multiple(
+                        rng,
+                        givens.div_ceil(2).min(cells / 2),
+                    ),
+                    None => (0..cells / 2)
+                        .filter(|_| rng.random_bool(0.1))
+                        .collect(),
+                };
+                pairs.into_iter().flat_map(|i| [i, cells - 1 - i]).collect()
             } else {
-                tango.restrictions.push(TangoRestriction::Different(a, b));
+                let givens = givens.unwrap_or_default().min(cells);
+                (0..cells).choose_multiple(rng, givens)
+            };
+            for i in given_cells {
+                tango.grid.tiles[i] = solution.tiles[i];
             }
+            return tango;
         }
-        // Randomly fill the grid with tiles
-        for y in 0..tango.grid.height {
-            for x in 0..tango.grid.width {
-                if random_bool(0.1) {
-                    if random_bool(0.5) {
-                        tango.set_tile(x, y, TangoTile::Red);
-                    } else {
-                        tango.set_tile(x, y, TangoTile::Blue);
-                    }
-                }
+    }
+
+    /// Generates a board without any givens, only restrictions. The rules
+    /// don't change when the colors are swapped, so such a board always has
+    /// at least two solutions; this returns one with exactly two, i.e. unique
+    /// up to swapping the colors. Such boards need many restrictions, so this
+    /// gives up after `max_attempts` boards, which a low
+    /// [`GeneratorConfig::max_restrictions`] quickly exhausts.
+    pub fn generate_restriction_only(
+        &self,
+        max_attempts: usize,
+    ) -> Option<Tango> {
+        let mut rng = self.rng();
+        (0..max_attempts).find_map(|_| {
+            let tango = self.generate_restrictions(&mut rng);
+            (self.solver.count_capped(&tango, 3) == 2).then_some(tango)
+        })
+    }
+
+    fn generate_restrictions(&self, rng: &mut impl Rng) -> Tango {
+        loop {
+            let tango = self.generate_restriction_set(rng);
+            let sames = tango
+                .restrictions
+                .iter()
+                .filter(|r| matches!(r, TangoRestriction::Same(..)))
+                .count();
+            let has_both = sames > 0 && sames < tango.restrictions.len();
+            if !self.config.require_both_restriction_kinds || has_both {
+                return tango;
             }
         }
+    }
 
+    fn generate_restriction_set(&self, mut rng: &mut impl Rng) -> Tango {
+        let mut tango = Tango::new(self.width, self.height, vec![])
+            .expect("Dimensions are checked by `TangoGenerator::new`");
+        let mut union_find = ParityUnionFind::new(self.width * self.height);
+
+        let same_ratio = self.config.same_ratio.clamp(0.0, 1.0);
+        let edges: Vec<_> = tango.edges().collect();
+        let max = self
+            .config
+            .max_restrictions
+            .unwrap_or(self.width * self.height / 4);
+        let to_take = rng.random_range(0..=edges.len().min(max));
+        for (a, b) in edges.into_iter().choose_multiple(&mut rng, to_take) {
+            let same = rng.random_bool(same_ratio);
+            if !union_find.union(
+                a.y * self.width + a.x,
+                b.y * self.width + b.x,
+                !same,
+            ) {
+                continue; // Would close an odd cycle of `Different`s
+            }
+            let restriction = if same {
+                TangoRestriction::Same(a, b)
+            } else {
+                TangoRestriction::Different(a, b)
+            };
+            tango
+                .add_restriction(restriction)
+                .expect("Neighbor pairs are adjacent and unique");
+        }
         tango
     }
 
-    pub fn generate_one_solution_tango() -> Tango {
-        let tango_generator = TangoGenerator::new(6, 6);
+    /// Generates boards until one has exactly one solution.
+    pub fn generate_unique(&self) -> Tango {
+        let mut rng = self.rng();
+        loop {
+            let tango = self.generate_from(&mut rng);
+            if self.solver.count_capped(&tango, 2) == 1 {
+                return tango;
+            }
+        }
+    }
 
-        let mut try_count = 0;
+    /// Generates boards until one is fully solved by [`Tango::autofill`], so
+    /// it can be solved by logic alone without ever guessing. Such a board
+    /// always has exactly one solution.
+    pub fn generate_logic_only(&self) -> Tango {
+        let mut rng = self.rng();
         loop {
-            try_count += 1;
-            println!("Attempt #{:06}", try_count);
-            let tango = tango_generator.generate();
-
-            let mut solver = RecursiveTangoSolver::new(tango.clone());
-            let solution_count = solver.solve(true);
-            if solution_count == 1 {
-                solver.solve(false);
-                println!(
-                    "Solution found!\nTotal solutions: {}",
-                    solution_count
-                );
-                println!("Tango:\n{}", &tango);
-                dbg!("Tango:\n{}", &tango);
+            let tango = self.generate_from(&mut rng);
+            if tango.is_logic_only() {
                 return tango;
             }
         }
     }
+
+    /// Generates boards until a unique one falls into `tier`, giving up
+    /// after `max_attempts` boards.
+    pub fn generate_with_tier(
+        &self,
+        tier: DifficultyTier,
+        max_attempts: usize,
+    ) -> Option<Tango> {
+        let mut rng = self.rng();
+        (0..max_attempts).find_map(|_| {
+            let tango = self.generate_from(&mut rng);
+            if self.solver.count_capped(&tango, 2) != 1 {
+                return None;
+            }
+            let score = tango.difficulty()?;
+            (self.thresholds.tier(score) == tier).then_some(tango)
+        })
+    }
+
+    /// Suggests tier thresholds from the scores of `samples` unique puzzles
+    /// of this generator, see [`TierThresholds::from_scores`]. `None` if
+    /// `samples` is zero.
+    pub fn calibrate_tiers(&self, samples: usize) -> Option<TierThresholds> {
+        let scores = self.difficulty_sample(samples);
+        TierThresholds::from_scores(scores)
+    }
+
+    /// Difficulty scores of `samples` unique puzzles.
+    pub fn difficulty_sample(&self, samples: usize) -> Vec<usize> {
+        let mut rng = self.rng();
+        let mut scores = Vec::with_capacity(samples);
+        while scores.len() < samples {
+            let tango = self.generate_from(&mut rng);
+            if self.solver.count_capped(&tango, 2) != 1 {
+                continue;
+            }
+            scores.extend(tango.difficulty());
+        }
+        scores
+    }
+
+    pub fn generate_one_solution_tango() -> Result<Tango, GenerationError> {
+        Ok(TangoGenerator::new(6, 6)?.generate_unique())
+    }
 }
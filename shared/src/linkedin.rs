@@ -0,0 +1,127 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+
+use crate::{Coord, Tango, TangoError, TangoRestriction, TangoTile};
+
+/// Puzzle layout exported by the LinkedIn Tango game. Cells are addressed as
+/// `[row, column]`, cell states are `0` for empty, `1` for a sun and `2` for
+/// a moon.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkedinTango {
+    grid_size: usize,
+    cell_states: Vec<Vec<u8>>,
+    edges: Vec<LinkedinEdge>,
+}
+
+#[derive(Deserialize)]
+struct LinkedinEdge {
+    from: [usize; 2],
+    to: [usize; 2],
+    sign: String,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Json(serde_json::Error),
+    Dimensions(&'static str),
+    RowLength(usize),
+    CellState(Coord, u8),
+    InvalidGiven(Coord),
+    Sign(String),
+    Tango(TangoError),
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Json(err) => write!(f, "Invalid JSON: {}", err),
+            ImportError::Dimensions(err) => write!(f, "{}", err),
+            ImportError::RowLength(row) => {
+                write!(f, "Row {} does not match the grid size.", row)
+            }
+            ImportError::CellState(cell, state) => {
+                write!(f, "Unknown cell state {} at {}.", state, cell)
+            }
+            ImportError::InvalidGiven(cell) => {
+                write!(f, "Given at {} breaks the rules.", cell)
+            }
+            ImportError::Sign(sign) => {
+                write!(f, "Unknown edge sign {:?}.", sign)
+            }
+            ImportError::Tango(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImportError::Json(err) => Some(err),
+            ImportError::Tango(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(err: serde_json::Error) -> Self {
+        ImportError::Json(err)
+    }
+}
+
+impl From<TangoError> for ImportError {
+    fn from(err: TangoError) -> Self {
+        ImportError::Tango(err)
+    }
+}
+
+impl Tango {
+    /// Imports a puzzle exported from the LinkedIn Tango game. Suns become
+    /// red tiles and moons blue ones.
+    pub fn from_linkedin_json(s: &str) -> Result<Tango, ImportError> {
+        let puzzle: LinkedinTango = serde_json::from_str(s)?;
+        let size = puzzle.grid_size;
+        let mut tango = Tango::new(size, size, vec![])?;
+
+        for edge in puzzle.edges {
+            let a = Coord::new(edge.from[1], edge.from[0]);
+            let b = Coord::new(edge.to[1], edge.to[0]);
+            let restriction = match edge.sign.as_str() {
+                "=" => TangoRestriction::Same(a, b),
+                "×" | "x" | "X" => TangoRestriction::Different(a, b),
+                _ => return Err(ImportError::Sign(edge.sign)),
+            };
+            tango.add_restriction(restriction)?;
+        }
+
+        if puzzle.cell_states.len() != size {
+            return Err(ImportError::Dimensions(
+                "Number of rows does not match the grid size.",
+            ));
+        }
+        for (y, row) in puzzle.cell_states.iter().enumerate() {
+            if row.len() != size {
+                return Err(ImportError::RowLength(y));
+            }
+            for (x, &state) in row.iter().enumerate() {
+                let tile = match state {
+                    0 => continue,
+                    1 => TangoTile::Red,
+                    2 => TangoTile::Blue,
+                    _ => {
+                        return Err(ImportError::CellState(
+                            Coord::new(x, y),
+                            state,
+                        ))
+                    }
+                };
+                if !tango.set_tile((x, y), tile) {
+                    return Err(ImportError::InvalidGiven(Coord::new(x, y)));
+                }
+            }
+        }
+        Ok(tango)
+    }
+}
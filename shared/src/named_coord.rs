@@ -0,0 +1,99 @@
+//! Alternative serde representation of [`Coord`] as `{ "x": .., "y": .. }`
+//! instead of an `[x, y]` pair, so JavaScript clients read named fields
+//! rather than indices that are easily swapped. Opt in per field with
+//! `#[serde(with = "ligames::named_coord")]`, or
+//! `#[serde(with = "ligames::named_coord::restrictions")]` for a list of
+//! restrictions.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Coord;
+
+/// A [`Coord`] serialized with named fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NamedCoord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl From<Coord> for NamedCoord {
+    fn from(Coord { x, y }: Coord) -> Self {
+        NamedCoord { x, y }
+    }
+}
+
+impl From<NamedCoord> for Coord {
+    fn from(NamedCoord { x, y }: NamedCoord) -> Self {
+        Coord { x, y }
+    }
+}
+
+pub fn serialize<S: Serializer>(
+    coord: &Coord,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    NamedCoord::from(*coord).serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Coord, D::Error> {
+    Ok(NamedCoord::deserialize(deserializer)?.into())
+}
+
+pub mod restrictions {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::NamedCoord;
+    use crate::TangoRestriction;
+
+    /// Same shape as [`TangoRestriction`] with named coordinates.
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    enum NamedRestriction {
+        Same(NamedCoord, NamedCoord),
+        Different(NamedCoord, NamedCoord),
+    }
+
+    impl From<&TangoRestriction> for NamedRestriction {
+        fn from(restriction: &TangoRestriction) -> Self {
+            match *restriction {
+                TangoRestriction::Same(a, b) => {
+                    NamedRestriction::Same(a.into(), b.into())
+                }
+                TangoRestriction::Different(a, b) => {
+                    NamedRestriction::Different(a.into(), b.into())
+                }
+            }
+        }
+    }
+
+    impl From<NamedRestriction> for TangoRestriction {
+        fn from(restriction: NamedRestriction) -> Self {
+            match restriction {
+                NamedRestriction::Same(a, b) => {
+                    TangoRestriction::Same(a.into(), b.into())
+                }
+                NamedRestriction::Different(a, b) => {
+                    TangoRestriction::Different(a.into(), b.into())
+                }
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        restrictions: &[TangoRestriction],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(restrictions.iter().map(NamedRestriction::from))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<TangoRestriction>, D::Error> {
+        Ok(Vec::<NamedRestriction>::deserialize(deserializer)?
+            .into_iter()
+            .map(TangoRestriction::from)
+            .collect())
+    }
+}
@@ -0,0 +1,51 @@
+//! Alternative serde representation of [`TangoTile`] where `Empty` is `null`
+//! and the colors stay `"Red"`/`"Blue"`. Opt in per field with
+//! `#[serde(with = "ligames::nullable_tile")]`, or
+//! `#[serde(with = "ligames::nullable_tile::vec")]` for a list of tiles such
+//! as the tiles of a grid.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::TangoTile;
+
+pub fn serialize<S: Serializer>(
+    tile: &TangoTile,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match tile {
+        TangoTile::Empty => serializer.serialize_none(),
+        tile => serializer.serialize_some(tile),
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<TangoTile, D::Error> {
+    Ok(Option::<TangoTile>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+pub mod vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::TangoTile;
+
+    pub fn serialize<S: Serializer>(
+        tiles: &[TangoTile],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            tiles
+                .iter()
+                .map(|tile| (*tile != TangoTile::Empty).then_some(tile)),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<TangoTile>, D::Error> {
+        Ok(Vec::<Option<TangoTile>>::deserialize(deserializer)?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+}
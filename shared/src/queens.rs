@@ -0,0 +1,399 @@
+use std::fmt::Display;
+
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{Coord, Grid};
+
+/// Smallest board where queens can be placed without touching.
+pub const QUEENS_MIN_SIZE: usize = 4;
+pub const QUEENS_MAX_SIZE: usize = 12;
+
+/// The LinkedIn Queens puzzle: a square board split into as many colored
+/// regions as it has rows. Every row, column and region gets exactly one
+/// queen, and no two queens may touch, not even diagonally.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "UncheckedQueens")]
+pub struct Queens {
+    /// Region of every cell, numbered from zero.
+    pub regions: Grid<usize>,
+    pub queens: Grid<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueensError {
+    /// The size is outside `QUEENS_MIN_SIZE..=QUEENS_MAX_SIZE`.
+    Size(usize),
+    NotSquare,
+    /// The regions aren't numbered `0..size`, or one of them is empty.
+    Regions,
+    /// A grid doesn't have exactly one entry per cell of the board.
+    Cells,
+    /// No board with exactly one solution was found in this many attempts.
+    Attempts(usize),
+}
+
+impl Display for QueensError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueensError::Size(size) => write!(
+                f,
+                "Size {} is not between {} and {}.",
+                size, QUEENS_MIN_SIZE, QUEENS_MAX_SIZE
+            ),
+            QueensError::NotSquare => write!(f, "The board must be square."),
+            QueensError::Regions => write!(
+                f,
+                "There must be one non-empty region per row, numbered from 0."
+            ),
+            QueensError::Cells => {
+                write!(f, "The grids must have one entry per cell.")
+            }
+            QueensError::Attempts(attempts) => write!(
+                f,
+                "No board with a unique solution was found in {} attempts.",
+                attempts
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QueensError {}
+
+impl Queens {
+    /// An empty board with the given regions.
+    pub fn new(regions: Grid<usize>) -> Result<Self, QueensError> {
+        let size = regions.width;
+        if regions.height != size {
+            return Err(QueensError::NotSquare);
+        }
+        if !(QUEENS_MIN_SIZE..=QUEENS_MAX_SIZE).contains(&size) {
+            return Err(QueensError::Size(size));
+        }
+        if regions.tiles.len() != size * size {
+            return Err(QueensError::Cells);
+        }
+        if (0..size).any(|region| regions.count(|r| *r == region) == 0)
+            || regions.count(|r| *r >= size) > 0
+        {
+            return Err(QueensError::Regions);
+        }
+        Ok(Queens {
+            queens: Grid::new(size, size),
+            regions,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.regions.width
+    }
+
+    /// Places or removes a queen, returning whether the cell exists.
+    pub fn set_queen(&mut self, coord: impl Into<Coord>, queen: bool) -> bool {
+        let Coord { x, y } = coord.into();
+        match self.queens.get_mut(x, y) {
+            Some(cell) => {
+                *cell = queen;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn placed(&self) -> Vec<Coord> {
+        self.queens
+            .iter_coords()
+            .filter(|(_, queen)| **queen)
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+
+    /// No row, column or region holds more than one queen and no two queens
+    /// touch.
+    pub fn is_valid(&self) -> bool {
+        let placed = self.placed();
+        placed.iter().enumerate().all(|(i, a)| {
+            placed[i + 1..].iter().all(|b| {
+                a.x != b.x
+                    && a.y != b.y
+                    && self.regions.get(a.x, a.y) != self.regions.get(b.x, b.y)
+                    && (a.x.abs_diff(b.x) > 1 || a.y.abs_diff(b.y) > 1)
+            })
+        })
+    }
+
+    /// A valid board with a queen in every row.
+    pub fn is_solved(&self) -> bool {
+        self.queens.count(|queen| *queen) == self.size() && self.is_valid()
+    }
+
+    /// Counts the solutions, ignoring queens already placed, stopping at
+    /// `cap`.
+    pub fn count_solutions(&self, cap: usize) -> usize {
+        QueensSearch::new(self).count_capped(cap)
+    }
+
+    /// Any solution of the board, ignoring queens already placed.
+    pub fn solution(&self) -> Option<Queens> {
+        let mut search = QueensSearch::new(self);
+        search.count_capped(1);
+        let columns = search.solutions.pop()?;
+        let mut solved = self.clone();
+        solved.queens.fill(false);
+        for (y, x) in columns.into_iter().enumerate() {
+            solved.set_queen((x, y), true);
+        }
+        Some(solved)
+    }
+}
+
+/// [`Queens`] as sent by clients, checked like [`Queens::new`] before use.
+#[derive(Deserialize)]
+struct UncheckedQueens {
+    regions: Grid<usize>,
+    queens: Grid<bool>,
+}
+
+impl TryFrom<UncheckedQueens> for Queens {
+    type Error = QueensError;
+
+    fn try_from(unchecked: UncheckedQueens) -> Result<Self, QueensError> {
+        let mut board = Queens::new(unchecked.regions)?;
+        let queens = unchecked.queens;
+        if (queens.width, queens.height) != (board.size(), board.size())
+            || queens.tiles.len() != queens.width * queens.height
+        {
+            return Err(QueensError::Cells);
+        }
+        board.queens = queens;
+        Ok(board)
+    }
+}
+
+/// Backtracking search placing one queen per row, top to bottom.
+struct QueensSearch<'a> {
+    queens: &'a Queens,
+    limit: usize,
+    /// Column of the queen in each filled row.
+    columns: Vec<usize>,
+    used_columns: Vec<bool>,
+    used_regions: Vec<bool>,
+    /// Columns of the queens of every solution found so far.
+    solutions: Vec<Vec<usize>>,
+}
+
+impl<'a> QueensSearch<'a> {
+    fn new(queens: &'a Queens) -> Self {
+        let size = queens.size();
+        QueensSearch {
+            queens,
+            limit: usize::MAX,
+            columns: Vec::with_capacity(size),
+            used_columns: vec![false; size],
+            used_regions: vec![false; size],
+            solutions: Vec::new(),
+        }
+    }
+
+    fn count_capped(&mut self, cap: usize) -> usize {
+        self.limit = cap;
+        self.solve_recursive(0)
+    }
+
+    fn solve_recursive(&mut self, mut acc: usize) -> usize {
+        let size = self.queens.size();
+        let y = self.columns.len();
+        if y == size {
+            self.solutions.push(self.columns.clone());
+            return acc + 1;
+        }
+        for x in 0..size {
+            let region = *self
+                .queens
+                .regions
+                .get(x, y)
+                .expect("Every cell has a region");
+            let touches_above = self
+                .columns
+                .last()
+                .is_some_and(|above| above.abs_diff(x) <= 1);
+            if self.used_columns[x]
+                || self.used_regions[region]
+                || touches_above
+            {
+                continue;
+            }
+            self.used_columns[x] = true;
+            self.used_regions[region] = true;
+            self.columns.push(x);
+            acc = self.solve_recursive(acc);
+            // Restore the state before going back up
+            self.columns.pop();
+            self.used_columns[x] = false;
+            self.used_regions[region] = false;
+            if acc >= self.limit {
+                return acc;
+            }
+        }
+        acc
+    }
+}
+
+/// Region layouts [`QueensGenerator::generate_unique`] tries by default. The
+/// largest boards need up to about a hundred.
+const DEFAULT_ATTEMPTS: usize = 200;
+
+pub struct QueensGenerator {
+    size: usize,
+    /// Region layouts tried before giving up.
+    max_attempts: usize,
+}
+
+impl QueensGenerator {
+    pub fn new(size: usize) -> Self {
+        QueensGenerator {
+            size,
+            max_attempts: DEFAULT_ATTEMPTS,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Generates regions with exactly one placement of queens, failing once
+    /// none of the tried layouts could be made unique.
+    pub fn generate_unique(&self) -> Result<Queens, QueensError> {
+        if !(QUEENS_MIN_SIZE..=QUEENS_MAX_SIZE).contains(&self.size) {
+            return Err(QueensError::Size(self.size));
+        }
+        let mut rng = rand::rng();
+        for _ in 0..self.max_attempts {
+            let (columns, queens) = self.generate_regions(&mut rng);
+            if let Some(queens) = self.make_unique(queens, &columns, &mut rng) {
+                return Ok(queens);
+            }
+        }
+        Err(QueensError::Attempts(self.max_attempts))
+    }
+
+    /// Breaks other solutions than the intended `columns` by moving one of
+    /// their queens' cells to a neighboring region, which then holds two of
+    /// their queens. Gives up after a while, as moves can open up new
+    /// solutions too.
+    fn make_unique(
+        &self,
+        mut queens: Queens,
+        columns: &[usize],
+        rng: &mut impl Rng,
+    ) -> Option<Queens> {
+        for _ in 0..self.size * self.size {
+            let mut search = QueensSearch::new(&queens);
+            search.count_capped(2);
+            let Some(other) =
+                search.solutions.into_iter().find(|s| s != columns)
+            else {
+                return Some(queens);
+            };
+            let mut cells: Vec<Coord> = other
+                .iter()
+                .enumerate()
+                .filter(|(y, x)| columns[*y] != **x)
+                .map(|(y, &x)| Coord::new(x, y))
+                .collect();
+            cells.shuffle(rng);
+            let moved = cells.into_iter().find_map(|cell| {
+                let region = *queens.regions.get(cell.x, cell.y)?;
+                let mut neighbors: Vec<usize> = queens
+                    .regions
+                    .neighbor_coords(cell)
+                    .filter_map(|n| queens.regions.get(n.x, n.y).copied())
+                    .filter(|r| *r != region)
+                    .collect();
+                neighbors.shuffle(rng);
+                let target = neighbors.into_iter().next()?;
+                let mut regions = queens.regions.clone();
+                *regions.get_mut(cell.x, cell.y)? = target;
+                is_connected(&regions, region).then_some(regions)
+            });
+            queens.regions = moved?;
+        }
+        None
+    }
+
+    /// Places non-touching queens at random and grows one region around
+    /// each of them, so the board has at least that solution. Returns the
+    /// column of the queen in each row together with the board.
+    fn generate_regions(&self, rng: &mut impl Rng) -> (Vec<usize>, Queens) {
+        let size = self.size;
+        let columns = loop {
+            let mut columns: Vec<usize> = (0..size).collect();
+            columns.shuffle(rng);
+            if columns.windows(2).all(|pair| pair[0].abs_diff(pair[1]) > 1) {
+                break columns;
+            }
+        };
+
+        let mut regions: Grid<Option<usize>> = Grid::new(size, size);
+        for (y, &x) in columns.iter().enumerate() {
+            *regions.get_mut(x, y).expect("Queens are on the board") = Some(y);
+        }
+        // Hands unassigned cells to a random neighboring region until every
+        // cell belongs to one
+        loop {
+            let frontier: Vec<(Coord, usize)> = regions
+                .iter_coords()
+                .filter(|(_, region)| region.is_none())
+                .filter_map(|(coord, _)| {
+                    let regions: Vec<usize> = regions
+                        .neighbor_coords(coord)
+                        .filter_map(|n| *regions.get(n.x, n.y)?)
+                        .collect();
+                    Some((coord, *regions.choose(rng)?))
+                })
+                .collect();
+            let Some(&(coord, region)) = frontier.choose(rng) else {
+                break;
+            };
+            *regions
+                .get_mut(coord.x, coord.y)
+                .expect("Frontier is on the board") = Some(region);
+        }
+
+        let regions = Grid {
+            width: size,
+            height: size,
+            tiles: regions.tiles.into_iter().flatten().collect(),
+        };
+        let queens =
+            Queens::new(regions).expect("Every region holds its queen");
+        (columns, queens)
+    }
+}
+
+/// Whether the cells of `region` form a single orthogonally connected area.
+fn is_connected(regions: &Grid<usize>, region: usize) -> bool {
+    let cells: Vec<Coord> = regions
+        .iter_coords()
+        .filter(|(_, r)| **r == region)
+        .map(|(coord, _)| coord)
+        .collect();
+    let Some(&start) = cells.first() else {
+        return false;
+    };
+    let mut seen = vec![start];
+    let mut stack = vec![start];
+    while let Some(cell) = stack.pop() {
+        for next in regions.neighbor_coords(cell) {
+            if regions.get(next.x, next.y) == Some(&region)
+                && !seen.contains(&next)
+            {
+                seen.push(next);
+                stack.push(next);
+            }
+        }
+    }
+    seen.len() == cells.len()
+}
@@ -0,0 +1,168 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Coord, Tango, TangoRestriction, TangoTile};
+
+const CELL: usize = 40;
+const MARGIN: usize = 10;
+
+/// Look of the tiles, shared by the SVG and the frontend. Each cell is
+/// filled with its color and shows its glyph, e.g. `"☀"` and `"☾"` for
+/// LinkedIn's sun and moon. The default draws plain red and blue squares.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    pub empty_glyph: String,
+    pub red_glyph: String,
+    pub blue_glyph: String,
+    /// CSS color of red tiles.
+    pub red_color: String,
+    /// CSS color of blue tiles.
+    pub blue_color: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            empty_glyph: String::new(),
+            red_glyph: String::new(),
+            blue_glyph: String::new(),
+            red_color: "#e53935".to_string(),
+            blue_color: "#1e88e5".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn glyph(&self, tile: TangoTile) -> &str {
+        match tile {
+            TangoTile::Empty => &self.empty_glyph,
+            TangoTile::Red => &self.red_glyph,
+            TangoTile::Blue => &self.blue_glyph,
+        }
+    }
+
+    /// Fill of the tile, empty ones are always white.
+    pub fn color(&self, tile: TangoTile) -> &str {
+        match tile {
+            TangoTile::Empty => "#ffffff",
+            TangoTile::Red => &self.red_color,
+            TangoTile::Blue => &self.blue_color,
+        }
+    }
+}
+
+/// Escapes text for use in XML content and attribute values.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Tango {
+    /// Same as the `Display` output, but with red and blue tiles colored for
+    /// terminals. Falls back to the plain output when `NO_COLOR` is set.
+    pub fn to_ansi(&self) -> String {
+        let colored = std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+        let mut out = String::new();
+        let _ = self.write_board(&mut out, colored, false);
+        out
+    }
+
+    /// Same as the `Display` output, with every restriction line starting
+    /// with `✓` when it holds, `✗` when it's broken and `…` while one of its
+    /// cells is empty.
+    pub fn display_with_restriction_status(&self) -> String {
+        let mut out = String::new();
+        let _ = self.write_board(&mut out, false, true);
+        out
+    }
+
+    /// Draws the board as a standalone SVG image. Restriction symbols are
+    /// drawn as lines, so the image does not depend on any fonts.
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with_theme(&Theme::default())
+    }
+
+    /// Same as [`Tango::to_svg`] in the colors of `theme`. Glyphs are drawn
+    /// as text, so only those depend on the fonts of the viewer.
+    pub fn to_svg_with_theme(&self, theme: &Theme) -> String {
+        let width = self.grid.width * CELL + 2 * MARGIN;
+        let height = self.grid.height * CELL + 2 * MARGIN;
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = write!(
+            svg,
+            r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+        );
+
+        for (Coord { x, y }, tile) in self.grid.iter_coords() {
+            let fill = escape_xml(theme.color(*tile));
+            let _ = write!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{CELL}" height="{CELL}" fill="{fill}" stroke="#cccccc" stroke-width="1"/>"##,
+                MARGIN + x * CELL,
+                MARGIN + y * CELL,
+            );
+            let glyph = theme.glyph(*tile);
+            if !glyph.is_empty() {
+                let _ = write!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="24" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    MARGIN + x * CELL + CELL / 2,
+                    MARGIN + y * CELL + CELL / 2,
+                    escape_xml(glyph),
+                );
+            }
+        }
+
+        for restriction in &self.restrictions {
+            let (a, b) = restriction.cells();
+            // Center of the edge shared by the two cells
+            let cx = MARGIN * 2 + (a.x + b.x + 1) * CELL;
+            let cy = MARGIN * 2 + (a.y + b.y + 1) * CELL;
+            let (cx, cy) = (cx as f64 / 2.0, cy as f64 / 2.0);
+            let lines = match restriction {
+                TangoRestriction::Same(_, _) => [
+                    (cx - 5.0, cy - 3.0, cx + 5.0, cy - 3.0),
+                    (cx - 5.0, cy + 3.0, cx + 5.0, cy + 3.0),
+                ],
+                TangoRestriction::Different(_, _) => [
+                    (cx - 4.0, cy - 4.0, cx + 4.0, cy + 4.0),
+                    (cx - 4.0, cy + 4.0, cx + 4.0, cy - 4.0),
+                ],
+            };
+            for (x1, y1, x2, y2) in lines {
+                let _ = write!(
+                    svg,
+                    r##"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="#333333" stroke-width="2"/>"##
+                );
+            }
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Rasterizes [`Tango::to_svg`] into PNG bytes.
+    #[cfg(feature = "image")]
+    pub fn to_png(&self) -> Option<Vec<u8>> {
+        use resvg::{tiny_skia, usvg};
+
+        let tree =
+            usvg::Tree::from_str(&self.to_svg(), &usvg::Options::default())
+                .ok()?;
+        let size = tree.size().to_int_size();
+        let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::default(),
+            &mut pixmap.as_mut(),
+        );
+        pixmap.encode_png().ok()
+    }
+}
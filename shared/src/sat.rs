@@ -0,0 +1,175 @@
+use std::fmt::Write;
+
+use crate::{BoardKind, Coord, Tango, TangoRestriction, TangoTile};
+
+/// CNF formula in DIMACS numbering, variables start at 1 and a negative
+/// literal is the negated variable.
+struct Cnf {
+    variables: i32,
+    clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    fn new_variable(&mut self) -> i32 {
+        self.variables += 1;
+        self.variables
+    }
+
+    /// At most `k` of the literals are true, using the sequential counter
+    /// encoding so the clause count stays linear in `literals.len() * k`.
+    fn at_most(&mut self, literals: &[i32], k: usize) {
+        let n = literals.len();
+        if k >= n {
+            return;
+        }
+        if k == 0 {
+            for &x in literals {
+                self.clauses.push(vec![-x]);
+            }
+            return;
+        }
+        // counter[i][j] is true once more than `j` of the first `i + 1`
+        // literals are true
+        let counter: Vec<Vec<i32>> = (0..n - 1)
+            .map(|_| (0..k).map(|_| self.new_variable()).collect())
+            .collect();
+        self.clauses.push(vec![-literals[0], counter[0][0]]);
+        for &s in &counter[0][1..] {
+            self.clauses.push(vec![-s]);
+        }
+        for i in 1..n - 1 {
+            let x = literals[i];
+            self.clauses.push(vec![-x, counter[i][0]]);
+            self.clauses.push(vec![-counter[i - 1][0], counter[i][0]]);
+            for j in 1..k {
+                self.clauses.push(vec![
+                    -x,
+                    -counter[i - 1][j - 1],
+                    counter[i][j],
+                ]);
+                self.clauses.push(vec![-counter[i - 1][j], counter[i][j]]);
+            }
+            self.clauses.push(vec![-x, -counter[i - 1][k - 1]]);
+        }
+        self.clauses
+            .push(vec![-literals[n - 1], -counter[n - 2][k - 1]]);
+    }
+
+    /// The literals can't all be true, nor all false.
+    fn not_all_equal(&mut self, literals: &[i32]) {
+        self.clauses.push(literals.iter().map(|x| -x).collect());
+        self.clauses.push(literals.to_vec());
+    }
+}
+
+impl Tango {
+    /// Variable of the cell, true meaning red.
+    fn cell_variable(&self, Coord { x, y }: Coord) -> i32 {
+        (y * self.grid.width + x + 1) as i32
+    }
+
+    /// Encodes the puzzle in DIMACS CNF for off-the-shelf SAT solvers. Cell
+    /// `(x, y)` is variable `y * width + x + 1`, true for red; any further
+    /// variables are auxiliary. Decode a model with
+    /// [`Tango::from_sat_assignment`].
+    pub fn to_dimacs(&self) -> String {
+        let (width, height) = (self.grid.width, self.grid.height);
+        let mut cnf = Cnf {
+            variables: (width * height) as i32,
+            clauses: Vec::new(),
+        };
+
+        let rows = (0..height).map(|y| {
+            let line: Vec<_> = (0..width).map(|x| Coord::new(x, y)).collect();
+            (line, self.rules.row_max_run)
+        });
+        let columns = (0..width).map(|x| {
+            let line: Vec<_> = (0..height).map(|y| Coord::new(x, y)).collect();
+            (line, self.rules.col_max_run)
+        });
+        for (line, max_run) in rows.chain(columns) {
+            let red: Vec<_> =
+                line.iter().map(|&c| self.cell_variable(c)).collect();
+            let blue: Vec<_> = red.iter().map(|x| -x).collect();
+            cnf.at_most(&red, line.len() / 2);
+            cnf.at_most(&blue, line.len() / 2);
+            for run in red.windows(max_run + 1) {
+                cnf.not_all_equal(run);
+            }
+        }
+
+        if self.rules.check_diagonals {
+            for y in 0..height.saturating_sub(2) {
+                for x in 0..width.saturating_sub(2) {
+                    let diagonal = [(x, y), (x + 1, y + 1), (x + 2, y + 2)];
+                    let anti_diagonal =
+                        [(x + 2, y), (x + 1, y + 1), (x, y + 2)];
+                    for cells in [diagonal, anti_diagonal] {
+                        cnf.not_all_equal(
+                            &cells.map(|c| self.cell_variable(c.into())),
+                        );
+                    }
+                }
+            }
+        }
+
+        for restriction in &self.restrictions {
+            let (a, b) = restriction.cells();
+            let (a, b) = (self.cell_variable(a), self.cell_variable(b));
+            match restriction {
+                TangoRestriction::Same(_, _) => {
+                    cnf.clauses.push(vec![a, -b]);
+                    cnf.clauses.push(vec![-a, b]);
+                }
+                TangoRestriction::Different(_, _) => {
+                    cnf.clauses.push(vec![a, b]);
+                    cnf.clauses.push(vec![-a, -b]);
+                }
+            }
+        }
+
+        for (coord, tile) in self.grid.iter_coords() {
+            let variable = self.cell_variable(coord);
+            match tile {
+                TangoTile::Red => cnf.clauses.push(vec![variable]),
+                TangoTile::Blue => cnf.clauses.push(vec![-variable]),
+                TangoTile::Empty => {}
+            }
+        }
+
+        let mut out = String::new();
+        let _ = writeln!(out, "c ligames tango {}x{}", width, height);
+        let _ = writeln!(out, "p cnf {} {}", cnf.variables, cnf.clauses.len());
+        for clause in &cnf.clauses {
+            for literal in clause {
+                let _ = write!(out, "{} ", literal);
+            }
+            out.push_str("0\n");
+        }
+        out
+    }
+
+    /// Fills the board from a SAT model of [`Tango::to_dimacs`], given as
+    /// the list of true and negated variables. Cells missing from the model
+    /// keep their current tile.
+    pub fn from_sat_assignment(&self, model: &[i32]) -> Tango {
+        let mut tango = self.clone();
+        let cells = (self.grid.width * self.grid.height) as i32;
+        for &literal in model {
+            let variable = literal.abs();
+            if variable == 0 || variable > cells {
+                continue; // Terminator or auxiliary variable
+            }
+            let index = (variable - 1) as usize;
+            tango.grid.tiles[index] = if literal > 0 {
+                TangoTile::Red
+            } else {
+                TangoTile::Blue
+            };
+        }
+        if tango.is_solved() {
+            tango.kind = BoardKind::Solution;
+        }
+        tango
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{BoardKind, RecursiveSearch, Tango};
+
+/// Which empty cell the backtracking search branches on next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BranchOrder {
+    /// The first empty cell, row by row.
+    #[default]
+    RowMajor,
+    /// A cell fitting at most one color if there is one, which fails or
+    /// fills dead ends early. Only helps without propagation, which already
+    /// fills such cells.
+    Mrv,
+    /// A cell whose restriction partner is already filled, which settles
+    /// the restriction right away.
+    RestrictionFirst,
+}
+
+/// A way of solving boards, so the search can be swapped out, e.g. for a
+/// SAT based one, without touching the generator or the server.
+pub trait TangoSolver {
+    /// Counts the solutions of the board, stopping at `cap`.
+    fn count_capped(&self, tango: &Tango, cap: usize) -> usize;
+
+    /// Any solution of the board, or `None` if it is unsolvable.
+    fn solution(&self, tango: &Tango) -> Option<Tango>;
+}
+
+/// The default backtracking solver, filling cells in row-major order unless
+/// another [`BranchOrder`] is picked.
+#[derive(Debug, Clone, Copy)]
+pub struct RecursiveTangoSolver {
+    /// Boards with at least this many cells fill forced tiles after every
+    /// placement. Smaller ones are solved by plain brute force, which is
+    /// faster there. Defaults to 64, i.e. 8x8 boards, around where
+    /// propagation starts paying off.
+    pub propagation_threshold: usize,
+    pub branch_order: BranchOrder,
+}
+
+impl Default for RecursiveTangoSolver {
+    fn default() -> Self {
+        RecursiveTangoSolver {
+            propagation_threshold: 64,
+            branch_order: BranchOrder::RowMajor,
+        }
+    }
+}
+
+impl RecursiveTangoSolver {
+    fn search<'a>(&self, tango: &'a mut Tango) -> RecursiveSearch<'a> {
+        let cells = tango.grid.width * tango.grid.height;
+        let search = RecursiveSearch::new(tango).ordered(self.branch_order);
+        if cells >= self.propagation_threshold {
+            search.propagating()
+        } else {
+            search
+        }
+    }
+}
+
+impl RecursiveTangoSolver {
+    /// Number of tiles tried while counting up to two solutions, for
+    /// comparing settings on the same board.
+    pub fn count_steps(&self, tango: &Tango) -> usize {
+        let mut board = tango.clone();
+        let mut search = self.search(&mut board);
+        search.count_capped(2);
+        search.steps
+    }
+}
+
+impl TangoSolver for RecursiveTangoSolver {
+    fn count_capped(&self, tango: &Tango, cap: usize) -> usize {
+        self.search(&mut tango.clone()).count_capped(cap)
+    }
+
+    fn solution(&self, tango: &Tango) -> Option<Tango> {
+        let mut board = tango.clone();
+        board.grid = self.search(&mut board.clone()).first_solution()?;
+        board.kind = BoardKind::Solution;
+        Some(board)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{SolveStep, Tango, TangoTile};
+
+/// Solves a board one tile at a time, e.g. for stepping through the search
+/// in a debugger. Each step places a forced tile or, if there is none,
+/// guesses red on the first empty cell. At a dead end the search can go back
+/// with [`SteppingSolver::backtrack`] or to any earlier
+/// [`SteppingSolver::snapshot`].
+#[derive(Debug, Clone)]
+pub struct SteppingSolver {
+    /// The board as given, which every restore starts from.
+    start: Tango,
+    board: Tango,
+    /// Every tile placed so far, in order.
+    trail: Vec<SolveStep>,
+    /// Positions in `trail` of the guesses whose other color wasn't tried.
+    guesses: Vec<usize>,
+}
+
+/// State of a [`SteppingSolver`], only meaningful to the solver it was taken
+/// from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolverSnapshot {
+    trail: Vec<SolveStep>,
+    guesses: Vec<usize>,
+}
+
+impl SteppingSolver {
+    pub fn new(tango: &Tango) -> Self {
+        SteppingSolver {
+            start: tango.clone(),
+            board: tango.clone(),
+            trail: Vec::new(),
+            guesses: Vec::new(),
+        }
+    }
+
+    pub fn board(&self) -> &Tango {
+        &self.board
+    }
+
+    /// The guesses on the way to the current board, oldest first.
+    pub fn guesses(&self) -> impl Iterator<Item = SolveStep> + '_ {
+        self.guesses.iter().map(|&index| self.trail[index])
+    }
+
+    pub fn snapshot(&self) -> SolverSnapshot {
+        SolverSnapshot {
+            trail: self.trail.clone(),
+            guesses: self.guesses.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &SolverSnapshot) {
+        self.board = self.start.clone();
+        for step in &snapshot.trail {
+            if let Some(tile) = self.board.grid.get_mut(step.x, step.y) {
+                *tile = step.tile;
+            }
+        }
+        self.trail = snapshot.trail.clone();
+        self.guesses = snapshot.guesses.clone();
+    }
+
+    /// Places a single tile, forced or guessed, and returns it. Returns
+    /// `None` once the board is full or at a dead end, where a cell fits
+    /// neither color. A full board is solved unless it breaks the diagonal
+    /// rule, which is only checked on complete boards.
+    pub fn step(&mut self) -> Option<SolveStep> {
+        let (step, guessed) = self.next_step()?;
+        if !self.board.set_tile((step.x, step.y), step.tile) {
+            return None; // Checked when choosing the step
+        }
+        if guessed {
+            self.guesses.push(self.trail.len());
+        }
+        self.trail.push(step);
+        Some(step)
+    }
+
+    /// Undoes everything since the latest guess and places its other color
+    /// instead, which is then no longer a guess. Goes further back while
+    /// the other color doesn't fit either. Returns `None` if no guess is
+    /// left, meaning the board has no solution on this path.
+    pub fn backtrack(&mut self) -> Option<SolveStep> {
+        while let Some(index) = self.guesses.pop() {
+            let guess = self.trail[index];
+            for step in self.trail.drain(index..) {
+                self.board.clear_tile((step.x, step.y));
+            }
+            let step = SolveStep {
+                tile: guess.tile.opposite()?,
+                ..guess
+            };
+            if self.board.set_tile((step.x, step.y), step.tile) {
+                self.trail.push(step);
+                return Some(step);
+            }
+        }
+        None
+    }
+
+    /// The first forced tile, else a guess on the first empty cell, telling
+    /// which of the two it is. `None` if a cell fits neither color.
+    fn next_step(&mut self) -> Option<(SolveStep, bool)> {
+        let mut forced = None;
+        let mut guess = None;
+        for y in 0..self.board.grid.height {
+            for x in 0..self.board.grid.width {
+                if self.board.get_tile((x, y)) != Some(TangoTile::Empty) {
+                    continue;
+                }
+                let red = self.board.fits(x, y, TangoTile::Red);
+                let blue = self.board.fits(x, y, TangoTile::Blue);
+                let tile = match (red, blue) {
+                    (false, false) => return None,
+                    (true, true) => {
+                        guess.get_or_insert(SolveStep {
+                            x,
+                            y,
+                            tile: TangoTile::Red,
+                        });
+                        continue;
+                    }
+                    (true, false) => TangoTile::Red,
+                    (false, true) => TangoTile::Blue,
+                };
+                forced.get_or_insert(SolveStep { x, y, tile });
+            }
+        }
+        match (forced, guess) {
+            (Some(step), _) => Some((step, false)),
+            (None, guess) => guess.map(|step| (step, true)),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    BoardKind, Coord, Grid, RecursiveSearch, Tango, TangoError,
+    TangoRestriction, TangoTile,
+};
+
+impl Tango {
+    /// The board mirrored and rotated in all eight ways of the square, each
+    /// also with the colors swapped. Turning the board by a quarter swaps
+    /// its width and height along with the run limits of rows and columns.
+    fn symmetric_images(&self) -> Vec<Tango> {
+        let (width, height) = (self.grid.width, self.grid.height);
+        let mut images = Vec::with_capacity(16);
+        for transposed in [false, true] {
+            for flip_x in [false, true] {
+                for flip_y in [false, true] {
+                    let map = |Coord { x, y }: Coord| {
+                        let x = if flip_x { width - 1 - x } else { x };
+                        let y = if flip_y { height - 1 - y } else { y };
+                        if transposed {
+                            Coord::new(y, x)
+                        } else {
+                            Coord::new(x, y)
+                        }
+                    };
+                    let image = self.mapped(map, transposed);
+                    let mut swapped = image.clone();
+                    for tile in &mut swapped.grid.tiles {
+                        *tile = tile.opposite().unwrap_or(TangoTile::Empty);
+                    }
+                    images.push(image);
+                    images.push(swapped);
+                }
+            }
+        }
+        images
+    }
+
+    /// Moves every tile and restriction from `coord` to `map(coord)`.
+    fn mapped(&self, map: impl Fn(Coord) -> Coord, transposed: bool) -> Tango {
+        let (width, height) = if transposed {
+            (self.grid.height, self.grid.width)
+        } else {
+            (self.grid.width, self.grid.height)
+        };
+        let mut grid = Grid::new(width, height);
+        for (coord, tile) in self.grid.iter_coords() {
+            let Coord { x, y } = map(coord);
+            grid.tiles[y * width + x] = *tile;
+        }
+        // Normalized and sorted, so images that only list their restrictions
+        // differently are the same board
+        let mut restrictions: Vec<TangoRestriction> = self
+            .restrictions
+            .iter()
+            .map(|restriction| restriction.map_cells(&map).normalized())
+            .collect();
+        restrictions.sort_unstable();
+        let mut rules = self.rules;
+        if transposed {
+            std::mem::swap(&mut rules.row_max_run, &mut rules.col_max_run);
+        }
+        Tango {
+            grid,
+            restrictions,
+            rules,
+            kind: self.kind,
+        }
+    }
+
+    /// Swaps two rows along with their restrictions, e.g. in an editor.
+    /// Fails without changing anything if a row is out of bounds, or if a
+    /// restriction to a neighboring row would end up between cells that
+    /// aren't adjacent.
+    pub fn swap_rows(
+        &mut self,
+        y1: usize,
+        y2: usize,
+    ) -> Result<(), TangoError> {
+        if let Some(y) = [y1, y2].into_iter().find(|&y| y >= self.grid.height) {
+            return Err(TangoError::OutOfBounds(Coord::new(0, y)));
+        }
+        let swap = |y| match y {
+            y if y == y1 => y2,
+            y if y == y2 => y1,
+            y => y,
+        };
+        self.remap_restrictions(|Coord { x, y }| Coord::new(x, swap(y)))?;
+        let swapped = self.grid.swap_rows(y1, y2);
+        debug_assert!(swapped, "Rows are checked to be in bounds");
+        Ok(())
+    }
+
+    /// Same as [`Tango::swap_rows`] for columns.
+    pub fn swap_columns(
+        &mut self,
+        x1: usize,
+        x2: usize,
+    ) -> Result<(), TangoError> {
+        if let Some(x) = [x1, x2].into_iter().find(|&x| x >= self.grid.width) {
+            return Err(TangoError::OutOfBounds(Coord::new(x, 0)));
+        }
+        let swap = |x| match x {
+            x if x == x1 => x2,
+            x if x == x2 => x1,
+            x => x,
+        };
+        self.remap_restrictions(|Coord { x, y }| Coord::new(swap(x), y))?;
+        let swapped = self.grid.swap_columns(x1, x2);
+        debug_assert!(swapped, "Columns are checked to be in bounds");
+        Ok(())
+    }
+
+    /// Moves the cells of every restriction, unless one would end up
+    /// between cells that aren't adjacent.
+    fn remap_restrictions(
+        &mut self,
+        map: impl Fn(Coord) -> Coord,
+    ) -> Result<(), TangoError> {
+        let restrictions = self
+            .restrictions
+            .iter()
+            .map(|restriction| {
+                let moved = restriction.map_cells(&map);
+                let (a, b) = moved.cells();
+                if a.x.abs_diff(b.x) + a.y.abs_diff(b.y) != 1 {
+                    return Err(TangoError::NotAdjacent(a, b));
+                }
+                Ok(moved)
+            })
+            .collect::<Result<_, _>>()?;
+        self.restrictions = restrictions;
+        Ok(())
+    }
+
+    /// The smallest of the board's mirror images, rotations and color swaps
+    /// by the order of [`Tango`]. Boards that are the same up to symmetry
+    /// share their canonical form.
+    pub fn canonical(&self) -> Tango {
+        self.symmetric_images()
+            .into_iter()
+            .min()
+            .expect("The board is one of its images")
+    }
+
+    /// Every solution of the board, keeping only the first one found of any
+    /// that are mirror images, rotations or color swaps of each other. Meant
+    /// for analysis of small boards, since all solutions are enumerated.
+    pub fn distinct_solutions(&self) -> Vec<Tango> {
+        let mut tango = self.clone();
+        let mut search = RecursiveSearch::new(&mut tango).collecting();
+        search.count_capped(usize::MAX);
+        let solutions = search.solutions.take().unwrap_or_default();
+
+        let mut seen = Vec::new();
+        let mut distinct = Vec::new();
+        for grid in solutions {
+            let solution = Tango {
+                grid,
+                kind: BoardKind::Solution,
+                ..self.clone()
+            };
+            let canonical = solution.canonical();
+            if !seen.contains(&canonical) {
+                seen.push(canonical);
+                distinct.push(solution);
+            }
+        }
+        distinct
+    }
+}
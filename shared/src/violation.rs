@@ -0,0 +1,233 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Coord, Tango, TangoRestriction, TangoTile};
+
+/// A broken rule together with the cells causing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub cells: Vec<Coord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViolationKind {
+    /// More than half of the row is a single color.
+    RowImbalance {
+        y: usize,
+        tile: TangoTile,
+    },
+    /// More than half of the column is a single color.
+    ColumnImbalance {
+        x: usize,
+        tile: TangoTile,
+    },
+    /// More same tiles next to each other in a row or column than the rules
+    /// allow.
+    Run {
+        tile: TangoTile,
+    },
+    /// Three same tiles along a diagonal, if the rule is enabled.
+    DiagonalRun {
+        tile: TangoTile,
+    },
+    Restriction(TangoRestriction),
+}
+
+/// A row or column of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+}
+
+/// Result of [`Tango::place_tile`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlacementResult {
+    /// Whether the cell exists, the tile is kept even when it breaks rules.
+    pub applied: bool,
+    /// The board breaks no rule after the placement.
+    pub valid: bool,
+    /// Every rule the board breaks after the placement.
+    pub violations: Vec<Violation>,
+    /// Lines through the cell that became complete with this placement, see
+    /// [`Tango::is_line_complete`].
+    pub completed_lines: Vec<Line>,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ViolationKind::RowImbalance { y, tile } => {
+                write!(f, "Row {} has too many {:?} tiles.", y, tile)
+            }
+            ViolationKind::ColumnImbalance { x, tile } => {
+                write!(f, "Column {} has too many {:?} tiles.", x, tile)
+            }
+            ViolationKind::Run { tile } => {
+                write!(f, "Too many {:?} tiles next to each other.", tile)
+            }
+            ViolationKind::DiagonalRun { tile } => {
+                write!(f, "Three {:?} tiles on a diagonal.", tile)
+            }
+            ViolationKind::Restriction(TangoRestriction::Same(a, b)) => {
+                write!(f, "Tiles {} and {} must be the same.", a, b)
+            }
+            ViolationKind::Restriction(TangoRestriction::Different(a, b)) => {
+                write!(f, "Tiles {} and {} must be different.", a, b)
+            }
+        }
+    }
+}
+
+impl Tango {
+    /// Places the tile even if it breaks a rule, as an editor would, and
+    /// reports what the board breaks afterwards. Unlike the solver's
+    /// placements, nothing is reverted.
+    pub fn place_tile(
+        &mut self,
+        x: usize,
+        y: usize,
+        tile: TangoTile,
+    ) -> PlacementResult {
+        let lines = [(true, y), (false, x)];
+        let complete_before =
+            lines.map(|(is_row, i)| self.is_line_complete(is_row, i));
+        let applied = match self.grid.get_mut(x, y) {
+            Some(existing) => {
+                *existing = tile;
+                true
+            }
+            None => false,
+        };
+        self.mark_played();
+        let violations = self.violations();
+        let completed_lines = lines
+            .into_iter()
+            .zip(complete_before)
+            .filter(|&((is_row, i), before)| {
+                !before && self.is_line_complete(is_row, i)
+            })
+            .map(|((is_row, i), _)| {
+                if is_row {
+                    Line::Row(i)
+                } else {
+                    Line::Column(i)
+                }
+            })
+            .collect();
+        PlacementResult {
+            applied,
+            valid: violations.is_empty(),
+            violations,
+            completed_lines,
+        }
+    }
+
+    /// Lists every rule the current board breaks.
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let (width, height) = (self.grid.width, self.grid.height);
+
+        for y in 0..height {
+            let line: Vec<_> = (0..width).map(|x| Coord::new(x, y)).collect();
+            let max_run = self.rules.row_max_run;
+            self.line_violations(&line, max_run, &mut violations, |tile| {
+                ViolationKind::RowImbalance { y, tile }
+            });
+        }
+        for x in 0..width {
+            let line: Vec<_> = (0..height).map(|y| Coord::new(x, y)).collect();
+            let max_run = self.rules.col_max_run;
+            self.line_violations(&line, max_run, &mut violations, |tile| {
+                ViolationKind::ColumnImbalance { x, tile }
+            });
+        }
+
+        for restriction in &self.restrictions {
+            let (a, b) = restriction.cells();
+            let Some((&tile_a, &tile_b)) =
+                self.grid.get_pair(a.into(), b.into())
+            else {
+                continue;
+            };
+            if tile_a == TangoTile::Empty || tile_b == TangoTile::Empty {
+                continue;
+            }
+            let broken = match restriction {
+                TangoRestriction::Same(_, _) => tile_a != tile_b,
+                TangoRestriction::Different(_, _) => tile_a == tile_b,
+            };
+            if broken {
+                violations.push(Violation {
+                    kind: ViolationKind::Restriction(restriction.clone()),
+                    cells: vec![a, b],
+                });
+            }
+        }
+
+        if self.rules.check_diagonals {
+            for y in 0..height.saturating_sub(2) {
+                for x in 0..width.saturating_sub(2) {
+                    let diagonal = [(x, y), (x + 1, y + 1), (x + 2, y + 2)];
+                    let anti_diagonal =
+                        [(x + 2, y), (x + 1, y + 1), (x, y + 2)];
+                    for cells in [diagonal, anti_diagonal] {
+                        let tiles = cells.map(|cell| self.get_tile(cell));
+                        if let Some(tile) = tiles[0] {
+                            if tile != TangoTile::Empty
+                                && tiles.iter().all(|t| *t == Some(tile))
+                            {
+                                violations.push(Violation {
+                                    kind: ViolationKind::DiagonalRun { tile },
+                                    cells: cells.map(Coord::from).to_vec(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    fn line_violations(
+        &self,
+        line: &[Coord],
+        max_run: usize,
+        violations: &mut Vec<Violation>,
+        imbalance: impl Fn(TangoTile) -> ViolationKind,
+    ) {
+        let tile_at =
+            |coord: Coord| self.get_tile(coord).unwrap_or(TangoTile::Empty);
+        for tile in [TangoTile::Red, TangoTile::Blue] {
+            let cells: Vec<_> = line
+                .iter()
+                .copied()
+                .filter(|&c| tile_at(c) == tile)
+                .collect();
+            if cells.len() > line.len() / 2 {
+                violations.push(Violation {
+                    kind: imbalance(tile),
+                    cells,
+                });
+            }
+        }
+
+        let mut start = 0;
+        while start < line.len() {
+            let tile = tile_at(line[start]);
+            let end = (start..line.len())
+                .find(|&i| tile_at(line[i]) != tile)
+                .unwrap_or(line.len());
+            if tile != TangoTile::Empty && end - start > max_run {
+                violations.push(Violation {
+                    kind: ViolationKind::Run { tile },
+                    cells: line[start..end].to_vec(),
+                });
+            }
+            start = end;
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use std::fmt::Display;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{Coord, Grid};
+
+pub const ZIP_MIN_SIZE: usize = 2;
+pub const ZIP_MAX_SIZE: usize = 8;
+
+/// The LinkedIn Zip puzzle: a single path has to visit every cell of the
+/// board exactly once, moving orthogonally, starting at cell `1` and passing
+/// the numbered cells in order to end on the highest one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Zip {
+    pub numbers: Grid<Option<usize>>,
+    /// The cells visited so far, in order. Left out of JSON while empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<Coord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZipError {
+    /// The size is outside `ZIP_MIN_SIZE..=ZIP_MAX_SIZE`.
+    Size(usize),
+    NotSquare,
+    /// The numbers aren't `1..=n` with `n` at least 2, each used once.
+    Numbers,
+}
+
+impl Display for ZipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ZipError::Size(size) => write!(
+                f,
+                "Size {} is not between {} and {}.",
+                size, ZIP_MIN_SIZE, ZIP_MAX_SIZE
+            ),
+            ZipError::NotSquare => write!(f, "The board must be square."),
+            ZipError::Numbers => write!(
+                f,
+                "The numbers must count up from 1 to at least 2 without gaps."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ZipError {}
+
+impl Zip {
+    /// A board with the given numbers and no path yet.
+    pub fn new(numbers: Grid<Option<usize>>) -> Result<Self, ZipError> {
+        let size = numbers.width;
+        if numbers.height != size {
+            return Err(ZipError::NotSquare);
+        }
+        if !(ZIP_MIN_SIZE..=ZIP_MAX_SIZE).contains(&size) {
+            return Err(ZipError::Size(size));
+        }
+        let mut used: Vec<usize> = numbers.iter().flatten().copied().collect();
+        used.sort_unstable();
+        if used.len() < 2 || used.iter().enumerate().any(|(i, n)| *n != i + 1) {
+            return Err(ZipError::Numbers);
+        }
+        Ok(Zip {
+            numbers,
+            path: Vec::new(),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.numbers.width
+    }
+
+    fn last_number(&self) -> usize {
+        self.numbers.count(Option::is_some)
+    }
+
+    fn number(&self, Coord { x, y }: Coord) -> Option<usize> {
+        self.numbers.get(x, y).copied().flatten()
+    }
+
+    /// The path visits every cell once, moving between neighbors, and
+    /// passes the numbers in order from `1` to the last one.
+    pub fn is_solved(&self) -> bool {
+        let cells = self.size() * self.size();
+        let mut visited = Grid::<bool>::new(self.size(), self.size());
+        let mut next_number = 1;
+        for (i, &cell) in self.path.iter().enumerate() {
+            let Some(seen) = visited.get_mut(cell.x, cell.y) else {
+                return false;
+            };
+            if *seen {
+                return false;
+            }
+            *seen = true;
+            if i > 0 {
+                let previous = self.path[i - 1];
+                if previous.x.abs_diff(cell.x) + previous.y.abs_diff(cell.y)
+                    != 1
+                {
+                    return false;
+                }
+            }
+            if let Some(number) = self.number(cell) {
+                if number != next_number {
+                    return false;
+                }
+                next_number += 1;
+            }
+        }
+        self.path.len() == cells
+            && next_number == self.last_number() + 1
+            && self.path.last().and_then(|cell| self.number(*cell))
+                == Some(self.last_number())
+    }
+
+    /// Counts the paths solving the board, ignoring the current path,
+    /// stopping at `cap`.
+    pub fn count_solutions(&self, cap: usize) -> usize {
+        ZipSearch::new(self).count_capped(cap)
+    }
+
+    /// Any solution of the board, ignoring the current path.
+    pub fn solution(&self) -> Option<Zip> {
+        let mut search = ZipSearch::new(self);
+        search.count_capped(1);
+        let path = search.solutions.pop()?;
+        Some(Zip {
+            path,
+            ..self.clone()
+        })
+    }
+}
+
+/// Depth-first search extending the path from cell `1`.
+struct ZipSearch<'a> {
+    zip: &'a Zip,
+    limit: usize,
+    path: Vec<Coord>,
+    visited: Grid<bool>,
+    next_number: usize,
+    /// Every solution found so far.
+    solutions: Vec<Vec<Coord>>,
+}
+
+impl<'a> ZipSearch<'a> {
+    fn new(zip: &'a Zip) -> Self {
+        ZipSearch {
+            zip,
+            limit: usize::MAX,
+            path: Vec::new(),
+            visited: Grid::new(zip.size(), zip.size()),
+            next_number: 1,
+            solutions: Vec::new(),
+        }
+    }
+
+    fn count_capped(&mut self, cap: usize) -> usize {
+        self.limit = cap;
+        let start = self
+            .zip
+            .numbers
+            .iter_coords()
+            .find(|(_, number)| **number == Some(1))
+            .map(|(coord, _)| coord);
+        match start {
+            Some(start) => self.visit(start, 0),
+            None => 0,
+        }
+    }
+
+    fn visit(&mut self, cell: Coord, acc: usize) -> usize {
+        let number = self.zip.number(cell);
+        if number.is_some_and(|number| number != self.next_number) {
+            return acc; // Numbers have to be passed in order
+        }
+        let cells = self.zip.size() * self.zip.size();
+        let last = self.zip.last_number();
+        if number == Some(last) && self.path.len() + 1 != cells {
+            return acc; // The last number has to end the path
+        }
+
+        self.path.push(cell);
+        self.visited.tiles[cell.y * self.zip.size() + cell.x] = true;
+        if number.is_some() {
+            self.next_number += 1;
+        }
+        let acc = if self.path.len() == cells {
+            self.solutions.push(self.path.clone());
+            acc + 1
+        } else if self.can_finish(cell) {
+            self.extend(cell, acc)
+        } else {
+            acc // Some cells can't be reached anymore
+        };
+        // Restore the state before going back up
+        if number.is_some() {
+            self.next_number -= 1;
+        }
+        self.visited.tiles[cell.y * self.zip.size() + cell.x] = false;
+        self.path.pop();
+        acc
+    }
+
+    fn extend(&mut self, cell: Coord, mut acc: usize) -> usize {
+        for next in self.visited.neighbor_coords(cell) {
+            if self.visited.get(next.x, next.y) != Some(&false) {
+                continue;
+            }
+            acc = self.visit(next, acc);
+            if acc >= self.limit {
+                break;
+            }
+        }
+        acc
+    }
+
+    /// Whether the path at `from` can still cover the board: every unvisited
+    /// cell has to be reachable, and only the cell of the last number, where
+    /// the path ends, may be a dead end.
+    fn can_finish(&self, from: Coord) -> bool {
+        let size = self.zip.size();
+        let last = self.zip.last_number();
+        let dead_end = self.visited.iter_coords().any(|(cell, visited)| {
+            let ways_in = self
+                .visited
+                .neighbor_coords(cell)
+                .filter(|&n| {
+                    self.visited.get(n.x, n.y) == Some(&false) || n == from
+                })
+                .count();
+            !visited && ways_in < 2 && self.zip.number(cell) != Some(last)
+        });
+        if dead_end {
+            return false;
+        }
+        let unvisited = self.visited.count(|visited| !visited);
+        let mut seen = Grid::<bool>::new(size, size);
+        let mut stack = vec![from];
+        let mut reached = 0;
+        while let Some(cell) = stack.pop() {
+            for next in self.visited.neighbor_coords(cell) {
+                if self.visited.get(next.x, next.y) == Some(&false)
+                    && seen.get(next.x, next.y) == Some(&false)
+                {
+                    seen.tiles[next.y * size + next.x] = true;
+                    reached += 1;
+                    stack.push(next);
+                }
+            }
+        }
+        reached == unvisited
+    }
+}
+
+pub struct ZipGenerator {
+    size: usize,
+}
+
+impl ZipGenerator {
+    pub fn new(size: usize) -> Self {
+        ZipGenerator { size }
+    }
+
+    /// Generates a board whose numbers allow exactly one path. Starts from a
+    /// random path numbering its ends and every `size`-th cell, then keeps
+    /// numbering a cell another solution visits out of turn until no other
+    /// is left. That ends at the latest with every cell numbered.
+    pub fn generate_unique(&self) -> Result<Zip, ZipError> {
+        if !(ZIP_MIN_SIZE..=ZIP_MAX_SIZE).contains(&self.size) {
+            return Err(ZipError::Size(self.size));
+        }
+        let mut rng = rand::rng();
+        let path = self.random_path(&mut rng);
+        let mut checkpoints: Vec<usize> = (0..path.len() - 1)
+            .step_by(self.size)
+            .chain([path.len() - 1])
+            .collect();
+        loop {
+            let zip = self.numbered(&path, &checkpoints);
+            let mut search = ZipSearch::new(&zip);
+            search.count_capped(2);
+            let Some(other) = search.solutions.into_iter().find(|s| *s != path)
+            else {
+                return Ok(zip);
+            };
+            let diverges = (0..path.len())
+                .find(|&i| other[i] != path[i] && !checkpoints.contains(&i))
+                .expect("Numbered cells are visited in turn");
+            checkpoints.push(diverges);
+            checkpoints.sort_unstable();
+        }
+    }
+
+    fn numbered(&self, path: &[Coord], checkpoints: &[usize]) -> Zip {
+        let mut numbers = Grid::new(self.size, self.size);
+        for (number, &i) in checkpoints.iter().enumerate() {
+            let Coord { x, y } = path[i];
+            *numbers.get_mut(x, y).expect("Path is on the board") =
+                Some(number + 1);
+        }
+        Zip::new(numbers).expect("Checkpoints are numbered in path order")
+    }
+
+    /// A random path through every cell, found by a randomized search that
+    /// prefers the neighbors with the fewest ways onward.
+    fn random_path(&self, rng: &mut impl Rng) -> Vec<Coord> {
+        let size = self.size;
+        // Colored like a chessboard, paths on odd boards start and end on
+        // the color of the corners, and on even boards one end is on each
+        // color. Starting on that color and reversing half of the time
+        // loses no paths either way.
+        let start = loop {
+            let (x, y) = (rng.random_range(0..size), rng.random_range(0..size));
+            if (x + y) % 2 == 0 {
+                break Coord::new(x, y);
+            }
+        };
+        let mut visited = Grid::<bool>::new(size, size);
+        let mut path = Vec::new();
+        if !self.extend_path(start, &mut visited, &mut path, rng) {
+            unreachable!("Every square board has a path from a corner color");
+        }
+        if rng.random_bool(0.5) {
+            path.reverse();
+        }
+        path
+    }
+
+    fn extend_path(
+        &self,
+        cell: Coord,
+        visited: &mut Grid<bool>,
+        path: &mut Vec<Coord>,
+        rng: &mut impl Rng,
+    ) -> bool {
+        visited.tiles[cell.y * self.size + cell.x] = true;
+        path.push(cell);
+        if path.len() == self.size * self.size {
+            return true;
+        }
+        let free = |visited: &Grid<bool>, Coord { x, y }: Coord| {
+            visited.get(x, y) == Some(&false)
+        };
+        let mut next: Vec<Coord> = visited
+            .neighbor_coords(cell)
+            .filter(|&n| free(visited, n))
+            .collect();
+        next.shuffle(rng);
+        next.sort_by_key(|&n| {
+            visited
+                .neighbor_coords(n)
+                .filter(|&n| free(visited, n))
+                .count()
+        });
+        for n in next {
+            if self.extend_path(n, visited, path, rng) {
+                return true;
+            }
+        }
+        visited.tiles[cell.y * self.size + cell.x] = false;
+        path.pop();
+        false
+    }
+}
@@ -0,0 +1,65 @@
+//! Puzzle analysis, see `Tango::analyze`.
+
+mod common;
+
+use common::board;
+
+#[test]
+fn unique_puzzle() {
+    let analysis = board(&["R.", ".."]).analyze();
+    assert!(analysis.unique);
+    assert!(analysis.satisfiable);
+    assert_eq!(analysis.solution_count_capped, 1);
+    assert!(analysis.difficulty.is_some());
+}
+
+#[test]
+fn ambiguous_puzzle() {
+    let analysis = board(&["..", ".."]).analyze();
+    assert!(!analysis.unique);
+    assert!(analysis.satisfiable);
+    assert_eq!(analysis.solution_count_capped, 2);
+    assert_eq!(analysis.difficulty, None);
+}
+
+#[test]
+fn broken_full_board_is_unsatisfiable() {
+    let tango = board(&["RRRR", "BBBB", "RRRR", "BBBB"]);
+    assert_eq!(tango.check_structure(), Ok(()));
+    assert!(!tango.is_valid());
+    let analysis = tango.analyze();
+    assert!(!analysis.unique);
+    assert!(!analysis.satisfiable);
+    assert_eq!(analysis.solution_count_capped, 0);
+    assert_eq!(analysis.difficulty, None);
+}
+
+#[test]
+fn broken_partial_board_is_unsatisfiable() {
+    let analysis = board(&["RRR.", "....", "....", "...."]).analyze();
+    assert!(!analysis.satisfiable);
+    assert_eq!(analysis.solution_count_capped, 0);
+}
+
+#[test]
+fn step_limit_gives_up() {
+    let tango = board(&["......"; 6]);
+    assert_eq!(tango.analyze_with_limit(10), None);
+}
+
+#[test]
+fn step_limit_above_the_work_matches_unlimited() {
+    let tango = board(&["R...", "....", "..B.", "...."]);
+    let mut limited = tango.analyze_with_limit(1_000_000).unwrap();
+    let mut unlimited = tango.analyze();
+    limited.metrics.elapsed_micros = None;
+    unlimited.metrics.elapsed_micros = None;
+    assert_eq!(limited, unlimited);
+}
+
+#[test]
+fn metrics_time_the_search() {
+    let metrics = board(&["R...", "....", "..B.", "...."]).solve_metrics();
+    assert!(metrics.steps > 0);
+    assert!(metrics.elapsed_micros.is_some());
+}
@@ -0,0 +1,25 @@
+//! Colored terminal output. Kept apart from the other tests, as it changes
+//! the `NO_COLOR` variable of the whole process.
+
+mod common;
+
+use common::board;
+
+#[test]
+fn no_color_disables_escapes() {
+    let tango = board(&["RB", ".."]);
+
+    std::env::remove_var("NO_COLOR");
+    let colored = tango.to_ansi();
+    assert!(colored.contains("\x1b[31m"));
+    assert!(colored.contains("\x1b[34m"));
+
+    std::env::set_var("NO_COLOR", "1");
+    let plain = tango.to_ansi();
+    assert!(!plain.contains('\x1b'));
+    assert_eq!(plain, tango.to_string());
+
+    // An empty value doesn't count as set
+    std::env::set_var("NO_COLOR", "");
+    assert_eq!(tango.to_ansi(), colored);
+}
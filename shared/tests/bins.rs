@@ -0,0 +1,61 @@
+//! Smoke tests running the binaries.
+
+use std::process::{Command, Stdio};
+
+use ligames::Tango;
+use serde_json::Value;
+
+#[test]
+fn demo_runs_once() {
+    let output = Command::new(env!("CARGO_BIN_EXE_demo"))
+        .arg("4")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Puzzle:\nTango Puzzle: 4x4"));
+    assert!(stdout.contains("Solution:\nTango Puzzle: 4x4"));
+    assert!(!stdout.contains("Press Enter"));
+}
+
+#[test]
+fn demo_watch_stops_at_the_end_of_input() {
+    let output = Command::new(env!("CARGO_BIN_EXE_demo"))
+        .args(["4", "--watch"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("Puzzle:\n").count(), 1);
+}
+
+#[test]
+fn demo_rejects_invalid_sizes() {
+    let output = Command::new(env!("CARGO_BIN_EXE_demo"))
+        .arg("5")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn dump_prints_one_line_per_puzzle() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ligames"))
+        .args(["dump", "--count", "5", "--size", "4"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    for line in lines {
+        let line: Value = serde_json::from_str(line).unwrap();
+        let mut puzzle: Tango =
+            serde_json::from_value(line["puzzle"].clone()).unwrap();
+        assert_eq!(puzzle.count_solutions(2), 1);
+        puzzle.grid = serde_json::from_value(line["solution"].clone()).unwrap();
+        assert!(puzzle.is_solved());
+    }
+}
@@ -0,0 +1,43 @@
+//! Board builders shared by the integration tests.
+#![allow(dead_code)]
+
+use ligames::{Tango, TangoRestriction, TangoTile};
+use serde_json::json;
+
+/// Tiles of rows written as `R`, `B` and `.` for empty cells.
+pub fn tiles(rows: &[&str]) -> Vec<TangoTile> {
+    rows.iter()
+        .flat_map(|row| row.chars())
+        .map(|c| match c {
+            'R' => TangoTile::Red,
+            'B' => TangoTile::Blue,
+            _ => TangoTile::Empty,
+        })
+        .collect()
+}
+
+/// A board without restrictions, see [`tiles`].
+pub fn board(rows: &[&str]) -> Tango {
+    restricted(rows, &[])
+}
+
+/// A board with the given restrictions, see [`tiles`].
+pub fn restricted(rows: &[&str], restrictions: &[TangoRestriction]) -> Tango {
+    serde_json::from_value(json!({
+        "grid": {
+            "width": rows[0].len(),
+            "height": rows.len(),
+            "tiles": tiles(rows),
+        },
+        "restrictions": restrictions,
+    }))
+    .unwrap()
+}
+
+pub fn same(a: (usize, usize), b: (usize, usize)) -> TangoRestriction {
+    TangoRestriction::Same(a.into(), b.into())
+}
+
+pub fn different(a: (usize, usize), b: (usize, usize)) -> TangoRestriction {
+    TangoRestriction::Different(a.into(), b.into())
+}
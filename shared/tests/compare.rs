@@ -0,0 +1,54 @@
+//! Comparing two states of a board.
+
+mod common;
+
+use common::{board, restricted, same};
+use ligames::{BoardKind, TangoError, TangoTile};
+
+#[test]
+fn identical_boards_have_no_diff() {
+    let tango = board(&["R.", ".B"]);
+    assert_eq!(tango.diff(&tango.clone()), Ok(vec![]));
+}
+
+#[test]
+fn diff_lists_the_changed_cell() {
+    let old = board(&["R...", "....", "B...", "...."]);
+    let new = board(&["R...", "....", "B..R", "...."]);
+    assert_eq!(
+        old.diff(&new),
+        Ok(vec![(3, 2, TangoTile::Empty, TangoTile::Red)])
+    );
+}
+
+#[test]
+fn diff_rejects_other_dimensions() {
+    let small = board(&["..", ".."]);
+    let wide = board(&["....", "...."]);
+    assert_eq!(
+        small.diff(&wide),
+        Err(TangoError::DimensionMismatch((2, 2), (4, 2)))
+    );
+}
+
+#[test]
+fn same_solution_ignores_metadata() {
+    let solution = board(&["RB", "BR"]);
+    let mut submitted = restricted(&["RB", "BR"], &[same((0, 0), (1, 1))]);
+    submitted.kind = BoardKind::InProgress;
+    submitted.rules.check_diagonals = true;
+    assert_ne!(submitted, solution);
+    assert!(submitted.same_solution(&solution));
+    assert!(solution.same_solution(&submitted));
+}
+
+#[test]
+fn same_solution_notices_a_single_tile() {
+    let solution = board(&["RBRB", "BRBR", "RBBR", "BRRB"]);
+    let mut submitted = solution.clone();
+    submitted.place_tile(3, 3, TangoTile::Red);
+    assert!(!submitted.same_solution(&solution));
+    submitted.place_tile(3, 3, TangoTile::Blue);
+    assert!(submitted.same_solution(&solution));
+    assert!(!board(&["RB", "BR"]).same_solution(&board(&["RBRB", "BRBR"])));
+}
@@ -0,0 +1,49 @@
+//! Explaining why a board has no solution.
+
+mod common;
+
+use common::{board, different, restricted, same};
+
+#[test]
+fn contradiction_cells_are_the_conflicting_placements() {
+    // The restrictions force (0, 0) and (2, 0) apart, (3, 3) is unrelated
+    let tango = restricted(
+        &["R.R.", "....", "....", "...R"],
+        &[same((0, 0), (1, 0)), different((1, 0), (2, 0))],
+    );
+    assert!(tango.is_valid());
+    assert_eq!(tango.contradiction_cells(), Some(vec![(0, 0), (2, 0)]));
+}
+
+#[test]
+fn contradiction_cells_without_restrictions() {
+    // Each tile alone is fine, together the first column needs two reds
+    let tango = board(&["R.", ".B"]);
+    assert!(tango.is_valid());
+    assert_eq!(tango.contradiction_cells(), Some(vec![(0, 0), (1, 1)]));
+}
+
+#[test]
+fn solvable_boards_have_no_contradiction() {
+    assert_eq!(board(&["R.", ".."]).contradiction_cells(), None);
+}
+
+#[test]
+fn minimal_conflict_keeps_only_the_contradictory_pair() {
+    let contradiction = [same((1, 1), (2, 1)), different((1, 1), (2, 1))];
+    let tango = restricted(
+        &["R...", "....", "....", "...."],
+        &[
+            different((0, 0), (0, 1)),
+            contradiction[0].clone(),
+            same((3, 2), (3, 3)),
+            contradiction[1].clone(),
+        ],
+    );
+    assert!(tango.is_valid());
+    assert_eq!(
+        tango.minimal_conflict(),
+        Some((vec![], contradiction.to_vec()))
+    );
+    assert_eq!(board(&["R.", ".."]).minimal_conflict(), None);
+}
@@ -0,0 +1,44 @@
+//! Cells addressed by `Coord`.
+
+mod common;
+
+use common::{restricted, same};
+use ligames::{Coord, TangoRestriction};
+use serde_json::json;
+
+#[test]
+fn converts_from_tuples() {
+    let coord = Coord::from((2, 5));
+    assert_eq!(coord, Coord::new(2, 5));
+    assert_eq!((coord.x, coord.y), (2, 5));
+    assert_eq!(<(usize, usize)>::from(coord), (2, 5));
+    assert_eq!(coord.to_string(), "(2, 5)");
+}
+
+#[test]
+fn serializes_as_pairs() {
+    assert_eq!(
+        serde_json::to_value(Coord::new(1, 3)).unwrap(),
+        json!([1, 3])
+    );
+    let restriction: TangoRestriction =
+        serde_json::from_value(json!({ "Different": [[0, 1], [0, 2]] }))
+            .unwrap();
+    assert_eq!(restriction.cells(), (Coord::new(0, 1), Coord::new(0, 2)));
+}
+
+#[test]
+fn restriction_endpoints_are_coords() {
+    let tango = restricted(&["..", ".."], &[same((0, 0), (1, 0))]);
+    let restrictions = tango.restrictions_for_cell(1, 0);
+    assert_eq!(restrictions.len(), 1);
+    assert_eq!(restrictions[0].cells().1, Coord { x: 1, y: 0 });
+}
+
+#[test]
+fn violations_name_cells_by_coord() {
+    let tango = restricted(&["RB", ".."], &[same((0, 0), (1, 0))]);
+    let messages: Vec<String> =
+        tango.violations().iter().map(ToString::to_string).collect();
+    assert_eq!(messages, ["Tiles (0, 0) and (1, 0) must be the same."]);
+}
@@ -0,0 +1,16 @@
+//! Step-by-step solving graded by technique.
+
+mod common;
+
+use common::board;
+use ligames::{DeductionLevel, TangoTile};
+
+#[test]
+fn line_rules_alone_grade_every_step_as_line() {
+    let mut tango = board(&["R.B.", ".B.R", "B..R", ".RB."]);
+    let empty = tango.count_total(TangoTile::Empty);
+    let steps = tango.solve_graded_steps();
+    assert_eq!(steps.len(), empty);
+    assert!(steps.iter().all(|&(_, level)| level == DeductionLevel::Line));
+    assert!(tango.is_solved());
+}
@@ -0,0 +1,85 @@
+//! Dimensions boards and generators accept.
+
+mod common;
+
+use common::board;
+use ligames::{
+    Coord, GenerationError, Tango, TangoError, TangoGenerator, MAX_DIMENSION,
+};
+
+#[test]
+fn largest_boards_are_accepted() {
+    assert_eq!(MAX_DIMENSION, 20);
+    assert!(Tango::check_dimensions(20, 20).is_ok());
+    assert!(Tango::check_dimensions(2, 20).is_ok());
+    assert!(TangoGenerator::new(20, 20).is_ok());
+}
+
+#[test]
+fn boards_beyond_the_maximum_are_rejected() {
+    for (width, height) in [(22, 22), (22, 2), (2, 22)] {
+        assert_eq!(
+            Tango::check_dimensions(width, height),
+            Err(TangoError::TooLarge)
+        );
+        assert_eq!(
+            TangoGenerator::new(width, height).err(),
+            Some(GenerationError::Tango(TangoError::TooLarge))
+        );
+    }
+}
+
+#[test]
+fn zero_dimensions_are_rejected() {
+    for (width, height) in [(0, 0), (0, 4), (4, 0)] {
+        assert_eq!(
+            TangoGenerator::new(width, height).err(),
+            Some(GenerationError::Tango(TangoError::ZeroDimension))
+        );
+    }
+}
+
+#[test]
+fn odd_dimensions_are_rejected() {
+    for (width, height) in [(3, 3), (3, 4), (4, 5), (1, 2)] {
+        assert_eq!(
+            TangoGenerator::new(width, height).err(),
+            Some(GenerationError::Tango(TangoError::OddDimension))
+        );
+    }
+}
+
+#[test]
+fn non_square_boards_generate_solve_and_serialize() {
+    for (width, height) in [(4, 6), (6, 4)] {
+        let tango = TangoGenerator::new(width, height)
+            .unwrap()
+            .generate_unique();
+        assert_eq!((tango.grid.width, tango.grid.height), (width, height));
+        assert_eq!(tango.count_solutions(2), 1);
+        let solution = tango.solution().unwrap();
+        assert!(solution.is_solved());
+        let json = serde_json::to_string(&tango).unwrap();
+        assert_eq!(serde_json::from_str::<Tango>(&json).unwrap(), tango);
+    }
+}
+
+#[test]
+fn non_square_edges_cover_the_board() {
+    let tango = board(&["......"; 8]);
+    let edges: Vec<_> = tango.edges().collect();
+    assert_eq!(edges.len(), 6 * 7 + 8 * 5);
+    for y in 0..8 {
+        for x in 0..6 {
+            let touching = edges
+                .iter()
+                .filter(|(a, b)| {
+                    *a == Coord::new(x, y) || *b == Coord::new(x, y)
+                })
+                .count();
+            let inner_x = usize::from(x > 0) + usize::from(x < 5);
+            let inner_y = usize::from(y > 0) + usize::from(y < 7);
+            assert_eq!(touching, inner_x + inner_y);
+        }
+    }
+}
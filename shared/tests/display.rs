@@ -0,0 +1,69 @@
+//! Text output of boards.
+
+mod common;
+
+use common::{board, different, restricted, same};
+use ligames::{Tango, Theme};
+use serde_json::json;
+
+#[test]
+fn displays_one_wide_boards() {
+    let tango = board(&["R", "B", "."]);
+    let text = tango.to_string();
+    assert!(text.starts_with("Tango Puzzle: 1x3"));
+    assert_eq!(text, tango.display_with_restriction_status());
+}
+
+#[test]
+fn displays_empty_boards() {
+    let tango: Tango = serde_json::from_value(json!({
+        "grid": { "width": 0, "height": 0, "tiles": [] },
+    }))
+    .unwrap();
+    assert!(tango.to_string().starts_with("Tango Puzzle: 0x0"));
+    let one_row = board(&[""]);
+    assert!(one_row.to_string().starts_with("Tango Puzzle: 0x1"));
+}
+
+#[test]
+fn marks_the_status_of_each_restriction() {
+    let tango = restricted(
+        &["RB..", "R...", "....", "...."],
+        &[
+            same((0, 0), (0, 1)),
+            same((0, 0), (1, 0)),
+            different((1, 0), (1, 1)),
+        ],
+    );
+    let text = tango.display_with_restriction_status();
+    let restrictions: Vec<&str> = text.lines().skip(2).take(3).collect();
+    assert_eq!(
+        restrictions,
+        [
+            "✓ Same: (0, 0) <-> (0, 1)",
+            "✗ Same: (0, 0) <-> (1, 0)",
+            "… Different: (1, 0) <-> (1, 1)",
+        ]
+    );
+    // The plain output has no markers
+    assert!(tango.to_string().contains("\nSame: (0, 0) <-> (0, 1)\n"));
+}
+
+#[test]
+fn svg_uses_the_theme_colors_and_glyphs() {
+    let theme = Theme {
+        red_glyph: "☀".to_string(),
+        blue_glyph: "☾".to_string(),
+        red_color: "#ffa000".to_string(),
+        blue_color: "#3949ab".to_string(),
+        ..Default::default()
+    };
+    let tango = board(&["RB", ".."]);
+    let svg = tango.to_svg_with_theme(&theme);
+    assert!(svg.contains(r##"fill="#ffa000""##));
+    assert!(svg.contains(r##"fill="#3949ab""##));
+    assert!(svg.contains('☀') && svg.contains('☾'));
+    let default = tango.to_svg();
+    assert!(default.contains(r##"fill="#e53935""##));
+    assert!(!default.contains("#ffa000"));
+}
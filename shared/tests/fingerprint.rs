@@ -0,0 +1,38 @@
+//! Content hashes of boards, see `Tango::fingerprint`.
+
+mod common;
+
+use common::{board, different, restricted, same};
+
+#[test]
+fn identical_puzzles_share_a_fingerprint() {
+    let restrictions = [same((0, 0), (1, 0)), different((1, 1), (1, 2))];
+    let a = restricted(&["R...", "....", "..B.", "...."], &restrictions);
+    let b = restricted(&["R...", "....", "..B.", "...."], &restrictions);
+    assert_eq!(a.fingerprint(), b.fingerprint());
+}
+
+#[test]
+fn restriction_order_and_direction_are_ignored() {
+    let a = restricted(
+        &["....", "....", "....", "...."],
+        &[same((0, 0), (1, 0)), different((1, 1), (1, 2))],
+    );
+    let b = restricted(
+        &["....", "....", "....", "...."],
+        &[different((1, 2), (1, 1)), same((1, 0), (0, 0))],
+    );
+    assert_eq!(a.fingerprint(), b.fingerprint());
+}
+
+#[test]
+fn different_puzzles_differ() {
+    let empty = board(&["....", "....", "....", "...."]);
+    let given = board(&["R...", "....", "....", "...."]);
+    let restricted =
+        restricted(&["....", "....", "....", "...."], &[same((0, 0), (1, 0))]);
+    let wide = board(&["......", "......", "......", "......"]);
+    assert_ne!(empty.fingerprint(), given.fingerprint());
+    assert_ne!(empty.fingerprint(), restricted.fingerprint());
+    assert_ne!(empty.fingerprint(), wide.fingerprint());
+}
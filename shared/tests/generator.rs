@@ -0,0 +1,240 @@
+//! Boards built by the generators.
+
+mod common;
+
+use std::error::Error;
+
+use common::board;
+use ligames::{
+    GenerationError, GeneratorConfig, Tango, TangoGenerator, TangoRestriction,
+    TangoTile,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn generator(config: GeneratorConfig) -> TangoGenerator {
+    TangoGenerator::new(6, 6)
+        .unwrap()
+        .with_config(config)
+        .unwrap()
+}
+
+#[test]
+fn generates_exactly_the_configured_givens() {
+    for givens in [0, 1, 7, 36] {
+        let generator = generator(GeneratorConfig {
+            givens: Some(givens),
+            ..Default::default()
+        });
+        for _ in 0..5 {
+            let tango = generator.generate();
+            assert_eq!(
+                tango.count_total(TangoTile::Empty),
+                36 - givens,
+                "{} givens",
+                givens
+            );
+        }
+    }
+}
+
+#[test]
+fn random_solutions_are_solved() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (width, height) in [(2, 2), (4, 4), (6, 6), (8, 6)] {
+        let tango = Tango::random_solution(width, height, &mut rng).unwrap();
+        assert_eq!(tango.grid.width, width);
+        assert_eq!(tango.grid.height, height);
+        assert!(tango.is_solved());
+    }
+    assert!(Tango::random_solution(3, 4, &mut rng).is_none());
+}
+
+#[test]
+fn generated_restrictions_are_satisfiable() {
+    let generator = TangoGenerator::new(6, 6).unwrap();
+    for _ in 0..20 {
+        assert!(generator.generate().restrictions_satisfiable());
+    }
+}
+
+#[test]
+fn skeleton_of_a_puzzle_without_givens_keeps_its_solutions() {
+    let generator = TangoGenerator::new(4, 4).unwrap().with_seed(1);
+    let tango = generator.generate_restriction_only(1000).unwrap();
+    let skeleton = tango.puzzle_skeleton();
+    assert_eq!(skeleton.count_total(TangoTile::Empty), 16);
+    assert_eq!(skeleton.restrictions(), tango.restrictions());
+    assert_eq!(skeleton.count_solutions(3), tango.count_solutions(3));
+}
+
+#[test]
+fn restriction_only_puzzles_are_unique_up_to_swapping_colors() {
+    let generator = TangoGenerator::new(4, 4).unwrap();
+    for _ in 0..5 {
+        let mut tango = generator.generate_restriction_only(1000).unwrap();
+        assert_eq!(tango.count_total(TangoTile::Empty), 16);
+        assert_eq!(tango.count_solutions(3), 2);
+        // Fixing one color leaves exactly one solution
+        assert!(tango.place_tile(0, 0, TangoTile::Red).valid);
+        assert_eq!(tango.count_solutions(3), 1);
+    }
+}
+
+#[test]
+fn restriction_only_generation_gives_up_without_restrictions() {
+    let generator = generator(GeneratorConfig {
+        max_restrictions: Some(0),
+        ..Default::default()
+    });
+    assert!(generator.generate_restriction_only(20).is_none());
+}
+
+#[test]
+fn randomly_filled_boards_break_no_rule() {
+    for size in [2, 4, 6, 8] {
+        let generator = TangoGenerator::new(size, size).unwrap();
+        for _ in 0..20 {
+            let tango = generator.generate();
+            assert!(tango.is_valid(), "{}", tango);
+        }
+    }
+}
+
+fn restrictions_with_ratio(same_ratio: f64) -> Vec<TangoRestriction> {
+    let generator = generator(GeneratorConfig {
+        same_ratio,
+        ..Default::default()
+    });
+    (0..20)
+        .flat_map(|_| generator.generate().restrictions().to_vec())
+        .collect()
+}
+
+#[test]
+fn same_ratio_picks_the_restriction_kinds() {
+    let sames = restrictions_with_ratio(1.0);
+    assert!(!sames.is_empty());
+    assert!(sames
+        .iter()
+        .all(|r| matches!(r, TangoRestriction::Same(..))));
+    let differents = restrictions_with_ratio(0.0);
+    assert!(!differents.is_empty());
+    assert!(differents
+        .iter()
+        .all(|r| matches!(r, TangoRestriction::Different(..))));
+}
+
+fn configured(config: GeneratorConfig) -> Result<Tango, Box<dyn Error>> {
+    Ok(TangoGenerator::new(4, 4)?.with_config(config)?.generate())
+}
+
+#[test]
+fn invalid_configs_are_errors() {
+    let err = configured(GeneratorConfig {
+        same_ratio: f64::NAN,
+        ..Default::default()
+    })
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(GenerationError::SameRatio(_))
+    ));
+
+    let err = configured(GeneratorConfig {
+        same_ratio: 1.0,
+        require_both_restriction_kinds: true,
+        ..Default::default()
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.downcast_ref(),
+        Some(&GenerationError::RestrictionKinds(1.0))
+    );
+    assert!(configured(GeneratorConfig::default()).is_ok());
+}
+
+#[test]
+fn puzzles_for_a_target_solve_to_the_target() {
+    // A picture that looks the same after a half turn
+    let target =
+        board(&["RRBRBB", "RRBBRB", "BBRRBR", "RBRRBB", "BRBBRR", "BBRBRR"]);
+    assert!(target.is_solved());
+    for seed in 0..5 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let puzzle = Tango::puzzle_for_solution(&target, &mut rng).unwrap();
+        assert_eq!(puzzle.count_solutions(2), 1);
+        let solved = puzzle.solution().unwrap();
+        for (coord, tile) in target.grid.iter_coords() {
+            assert_eq!(solved.get_tile(coord), Some(*tile), "{coord}");
+        }
+    }
+
+    let unsolved = board(&["R.....", "......", "......", "......"]);
+    assert!(matches!(
+        Tango::puzzle_for_solution(&unsolved, &mut StdRng::seed_from_u64(0)),
+        Err(GenerationError::NotSolved)
+    ));
+}
+
+#[test]
+fn boards_can_be_required_to_have_both_restriction_kinds() {
+    // Mostly `Different`s, so boards without a `Same` would be common
+    let generator = generator(GeneratorConfig {
+        same_ratio: 0.1,
+        require_both_restriction_kinds: true,
+        ..Default::default()
+    });
+    for _ in 0..20 {
+        let tango = generator.generate();
+        let restrictions = tango.restrictions();
+        assert!(restrictions
+            .iter()
+            .any(|r| matches!(r, TangoRestriction::Same(..))));
+        assert!(restrictions
+            .iter()
+            .any(|r| matches!(r, TangoRestriction::Different(..))));
+    }
+}
+
+#[test]
+fn symmetric_givens_survive_a_half_turn() {
+    for givens in [None, Some(7), Some(12)] {
+        let generator = generator(GeneratorConfig {
+            givens,
+            symmetric_givens: true,
+            ..Default::default()
+        });
+        for _ in 0..10 {
+            let tango = generator.generate();
+            let filled: Vec<bool> = tango
+                .grid
+                .tiles
+                .iter()
+                .map(|tile| *tile != TangoTile::Empty)
+                .collect();
+            let turned: Vec<bool> = filled.iter().rev().copied().collect();
+            assert_eq!(filled, turned, "{givens:?} givens");
+            if let Some(givens) = givens {
+                // Odd numbers are rounded up to whole pairs
+                let pairs = givens.div_ceil(2);
+                assert_eq!(tango.count_total(TangoTile::Empty), 36 - 2 * pairs);
+            }
+        }
+    }
+}
+
+#[test]
+fn restrictions_stay_within_the_cap() {
+    for (max_restrictions, cap) in [(Some(0), 0), (Some(3), 3), (None, 9)] {
+        let generator = generator(GeneratorConfig {
+            max_restrictions,
+            ..Default::default()
+        });
+        for _ in 0..20 {
+            let count = generator.generate().restrictions().len();
+            assert!(count <= cap, "{count} restrictions, cap {cap}");
+        }
+        let count = generator.generate_unique().restrictions().len();
+        assert!(count <= cap, "{count} restrictions, cap {cap}");
+    }
+}
@@ -0,0 +1,155 @@
+//! Generic grid helpers.
+
+use ligames::{Coord, Grid, TangoError};
+
+#[test]
+fn neighbor_coords_stay_on_the_grid() {
+    let grid = Grid {
+        width: 3,
+        height: 2,
+        tiles: vec![false; 6],
+    };
+    let around = |x, y| -> Vec<Coord> {
+        grid.neighbor_coords(Coord::new(x, y)).collect()
+    };
+    assert_eq!(around(0, 0), [Coord::new(1, 0), Coord::new(0, 1)]);
+    assert_eq!(
+        around(1, 1),
+        [Coord::new(1, 0), Coord::new(2, 1), Coord::new(0, 1)]
+    );
+    assert_eq!(around(2, 1), [Coord::new(2, 0), Coord::new(1, 1)]);
+}
+
+fn grid() -> Grid<u8> {
+    Grid {
+        width: 3,
+        height: 2,
+        tiles: vec![1, 2, 3, 4, 5, 6],
+    }
+}
+
+#[test]
+fn iterates_by_reference_in_row_major_order() {
+    let grid = grid();
+    let tiles: Vec<u8> = (&grid).into_iter().copied().collect();
+    assert_eq!(tiles, grid.tiles);
+    let mut sum = 0;
+    for tile in &grid {
+        sum += tile;
+    }
+    assert_eq!(sum, 21);
+}
+
+#[test]
+fn get_and_get_mut_agree_on_bounds() {
+    let mut grid = grid();
+    let coords = [0, 1, 2, 3, usize::MAX / 2, usize::MAX];
+    for x in coords {
+        for y in coords {
+            let tile = grid.get(x, y).copied();
+            assert_eq!(grid.get_mut(x, y).map(|tile| *tile), tile);
+            assert_eq!(tile.is_some(), x < 3 && y < 2, "({}, {})", x, y);
+        }
+    }
+    assert_eq!(grid.get(2, 1), Some(&6));
+    *grid.get_mut(2, 1).unwrap() = 0;
+    assert_eq!(grid.tiles[5], 0);
+}
+
+#[test]
+fn converts_rectangular_rows() {
+    let rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    assert_eq!(Grid::try_from(rows), Ok(grid()));
+}
+
+#[test]
+fn rejects_jagged_rows() {
+    let rows = vec![vec![1, 2], vec![3, 4], vec![5]];
+    assert_eq!(Grid::try_from(rows), Err(TangoError::JaggedRow(2)));
+}
+
+#[test]
+fn rejects_empty_rows() {
+    let rows: Vec<Vec<u8>> = vec![];
+    assert_eq!(Grid::try_from(rows), Err(TangoError::ZeroDimension));
+    assert_eq!(
+        Grid::<u8>::try_from(vec![vec![], vec![]]),
+        Err(TangoError::ZeroDimension)
+    );
+}
+
+#[test]
+fn fill_sets_every_tile() {
+    let mut grid = grid();
+    grid.fill(9);
+    assert_eq!(grid.tiles, [9; 6]);
+    assert_eq!((grid.width, grid.height), (3, 2));
+}
+
+#[test]
+fn count_matches_a_manual_tally() {
+    let grid = grid();
+    let mut even = 0;
+    for tile in &grid {
+        if tile % 2 == 0 {
+            even += 1;
+        }
+    }
+    assert_eq!(grid.count(|tile| tile % 2 == 0), even);
+    assert_eq!(grid.count(|_| true), 6);
+    assert_eq!(grid.count(|&tile| tile > 6), 0);
+}
+
+#[test]
+fn neighbors_are_none_past_the_edges() {
+    let grid =
+        Grid::try_from(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]])
+            .unwrap();
+    // Above, right, below and left
+    assert_eq!(
+        grid.neighbors(1, 1),
+        [Some(&2), Some(&6), Some(&8), Some(&4)]
+    );
+    assert_eq!(grid.neighbors(1, 0), [None, Some(&3), Some(&5), Some(&1)]);
+    assert_eq!(grid.neighbors(0, 1), [Some(&1), Some(&5), Some(&7), None]);
+    assert_eq!(grid.neighbors(0, 0), [None, Some(&2), Some(&4), None]);
+    assert_eq!(grid.neighbors(2, 2), [Some(&6), None, None, Some(&8)]);
+}
+
+#[test]
+fn get_pair_needs_both_tiles_on_the_grid() {
+    let grid = grid();
+    assert_eq!(grid.get_pair((0, 0), (2, 1)), Some((&1, &6)));
+    assert_eq!(grid.get_pair((1, 1), (1, 1)), Some((&5, &5)));
+    assert_eq!(grid.get_pair((0, 0), (3, 0)), None);
+    assert_eq!(grid.get_pair((0, 2), (0, 0)), None);
+    assert_eq!(grid.get_pair((3, 0), (usize::MAX, 2)), None);
+}
+
+#[test]
+fn swapping_twice_restores_the_grid() {
+    let mut grid = grid();
+    assert!(grid.swap((0, 0), (2, 1)));
+    assert_eq!(grid.tiles, [6, 2, 3, 4, 5, 1]);
+    assert!(grid.swap((2, 1), (0, 0)));
+    assert_eq!(grid, self::grid());
+
+    assert!(grid.swap_rows(0, 1));
+    assert_eq!(grid.tiles, [4, 5, 6, 1, 2, 3]);
+    assert!(grid.swap_rows(0, 1));
+    assert_eq!(grid, self::grid());
+
+    assert!(grid.swap_columns(0, 2));
+    assert_eq!(grid.tiles, [3, 2, 1, 6, 5, 4]);
+    assert!(grid.swap_columns(2, 0));
+    assert_eq!(grid, self::grid());
+}
+
+#[test]
+fn swaps_out_of_bounds_change_nothing() {
+    let mut grid = grid();
+    assert!(!grid.swap((0, 0), (3, 0)));
+    assert!(!grid.swap_rows(0, 2));
+    assert!(!grid.swap_columns(3, 0));
+    assert_eq!(grid, self::grid());
+}
@@ -0,0 +1,65 @@
+//! Queries on single rows and columns.
+
+mod common;
+
+use common::board;
+use ligames::{Line, TangoTile};
+
+#[test]
+fn counts_colors_in_a_partial_row() {
+    let tango = board(&["R.BR..", "......", "B.....", "......"]);
+    assert_eq!(tango.count_in_row(0, TangoTile::Red), 2);
+    assert_eq!(tango.count_in_row(0, TangoTile::Blue), 1);
+    assert_eq!(tango.count_in_row(0, TangoTile::Empty), 3);
+    assert_eq!(tango.count_in_row(1, TangoTile::Red), 0);
+    assert_eq!(tango.count_in_column(0, TangoTile::Red), 1);
+    assert_eq!(tango.count_in_column(0, TangoTile::Blue), 1);
+    // Out of bounds lines hold nothing
+    assert_eq!(tango.count_in_row(4, TangoTile::Empty), 0);
+    assert_eq!(tango.count_in_column(6, TangoTile::Empty), 0);
+}
+
+#[test]
+fn partial_lines_that_can_still_be_balanced() {
+    let tango = board(&["R..R..", "RR....", "......", "......"]);
+    assert!(tango.line_balanceable(true, 0));
+    assert!(tango.line_balanceable(true, 1));
+    assert!(tango.line_balanceable(true, 2));
+    assert!(tango.line_balanceable(false, 0));
+}
+
+#[test]
+fn partial_lines_that_are_doomed() {
+    // No rule is broken yet, but the middle needs three of the other color
+    let tango = board(&["RR...R", "BB...B", "......", "......"]);
+    assert!(tango.is_valid());
+    assert!(!tango.line_balanceable(true, 0));
+    assert!(!tango.line_balanceable(true, 1));
+    assert!(!tango.line_balanceable(true, 9));
+}
+
+#[test]
+fn complete_lines_are_full_and_valid() {
+    let mut tango = board(&["RBRB", "RRRB", "RB..", "...."]);
+    assert!(tango.is_line_complete(true, 0));
+    // Full, but with a run of three and too many reds
+    assert!(!tango.is_line_complete(true, 1));
+    assert!(!tango.is_line_complete(true, 2));
+    assert!(!tango.is_line_complete(false, 0));
+    assert!(!tango.is_line_complete(true, 4));
+
+    let placed = tango.place_tile(2, 2, TangoTile::Blue);
+    assert!(placed.completed_lines.is_empty());
+    let placed = tango.place_tile(3, 2, TangoTile::Red);
+    assert_eq!(placed.completed_lines, [Line::Row(2)]);
+    assert!(tango.is_line_complete(true, 2));
+}
+
+#[test]
+fn line_remaining_matches_a_manual_tally() {
+    let tango = board(&["R.BR", "....", "RRR.", "B..."]);
+    let (rows, columns) = tango.line_remaining();
+    // The third row already has too many reds, so it needs none
+    assert_eq!(rows, [(0, 1), (2, 2), (0, 2), (2, 1)]);
+    assert_eq!(columns, [(0, 1), (1, 2), (1, 1), (1, 2)]);
+}
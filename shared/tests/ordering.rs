@@ -0,0 +1,82 @@
+//! The total order on boards used for canonicalization.
+
+mod common;
+
+use std::cmp::Ordering;
+
+use common::{board, different, restricted, same};
+use ligames::{Tango, TangoGenerator};
+
+fn boards() -> Vec<Tango> {
+    let mut boards: Vec<Tango> = (0..5)
+        .map(|seed| {
+            TangoGenerator::new(4, 4)
+                .unwrap()
+                .with_seed(seed)
+                .generate()
+        })
+        .collect();
+    boards.extend([
+        board(&["..", ".."]),
+        board(&["R.", ".."]),
+        board(&["B.", ".."]),
+        board(&["......"; 2]),
+        restricted(&["..", ".."], &[same((0, 0), (1, 0))]),
+        restricted(&["..", ".."], &[different((0, 0), (1, 0))]),
+        restricted(
+            &["..", ".."],
+            &[same((0, 0), (1, 0)), different((0, 1), (1, 1))],
+        ),
+        restricted(
+            &["..", ".."],
+            &[different((0, 1), (1, 1)), same((0, 0), (1, 0))],
+        ),
+    ]);
+    boards
+}
+
+#[test]
+fn order_is_total() {
+    let boards = boards();
+    for a in &boards {
+        for b in &boards {
+            assert_eq!(a.cmp(b), b.cmp(a).reverse());
+            assert_eq!(a.cmp(b) == Ordering::Equal, a == b);
+            for c in &boards {
+                if a <= b && b <= c {
+                    assert!(a <= c);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn order_is_stable_across_clones() {
+    let boards = boards();
+    for tango in &boards {
+        assert_eq!(tango.cmp(&tango.clone()), Ordering::Equal);
+    }
+    let mut sorted = boards.clone();
+    sorted.sort();
+    let mut reversed: Vec<Tango> = boards.iter().rev().cloned().collect();
+    reversed.sort();
+    assert_eq!(sorted, reversed);
+}
+
+#[test]
+fn restrictions_compare_in_any_cell_order() {
+    let forward = restricted(
+        &["R.", ".."],
+        &[same((0, 0), (1, 0)), different((0, 1), (1, 1))],
+    );
+    let backward = restricted(
+        &["R.", ".."],
+        &[different((1, 1), (0, 1)), same((1, 0), (0, 0))],
+    );
+    assert_eq!(forward.cmp(&backward), Ordering::Equal);
+    assert_eq!(forward, backward);
+
+    let other = restricted(&["R.", ".."], &[same((1, 0), (0, 0))]);
+    assert_ne!(forward.cmp(&other), Ordering::Equal);
+}
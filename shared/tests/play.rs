@@ -0,0 +1,84 @@
+//! Actions taken on boards while playing.
+
+mod common;
+
+use common::{board, tiles};
+use ligames::{Coord, TangoGenerator, TangoTile};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn autofill_completes_logic_only_puzzles() {
+    let generator = TangoGenerator::new(6, 6).unwrap();
+    for _ in 0..5 {
+        let mut tango = generator.generate_logic_only();
+        let empty = tango.count_total(TangoTile::Empty);
+        assert_eq!(tango.autofill(), empty);
+        assert!(tango.is_solved());
+    }
+}
+
+#[test]
+fn autofill_only_places_forced_tiles() {
+    let mut tango = board(&["R.R.", "....", "....", "...."]);
+    assert_eq!(tango.autofill(), 2);
+    assert_eq!(tango.grid.tiles[..4], tiles(&["RBRB"]));
+}
+
+#[test]
+fn revealed_tiles_match_the_unique_solution() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut tango = TangoGenerator::new(6, 6)
+        .unwrap()
+        .with_seed(3)
+        .generate_unique();
+    let solution = tango.solution().unwrap();
+    while let Some((coord, tile)) = tango.reveal(&mut rng) {
+        assert_eq!(tango.get_tile(coord), Some(TangoTile::Empty));
+        assert_eq!(solution.get_tile(coord), Some(tile));
+        assert!(tango.place_tile(coord.x, coord.y, tile).valid);
+    }
+    assert_eq!(tango.grid, solution.grid);
+}
+
+#[test]
+fn ambiguous_boards_reveal_nothing() {
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(board(&["..", ".."]).reveal(&mut rng), None);
+}
+
+#[test]
+fn moves_to_solution_counts_correct_partial_boards() {
+    let mut tango = board(&["R.B.", ".B.R", "B..R", ".RB."]);
+    assert_eq!(tango.moves_to_solution(), Some(8));
+    let solution = tango.solution().unwrap();
+    tango.place_tile(1, 0, solution.get_tile((1, 0)).unwrap());
+    tango.place_tile(0, 1, solution.get_tile((0, 1)).unwrap());
+    assert_eq!(tango.moves_to_solution(), Some(6));
+}
+
+#[test]
+fn moves_to_solution_rejects_contradicting_boards() {
+    let mut tango = board(&["R.B.", ".B.R", "B..R", ".RB."]);
+    let wrong = tango.solution().unwrap().get_tile((1, 0)).unwrap();
+    tango.place_tile(1, 0, wrong.opposite().unwrap());
+    assert_eq!(tango.moves_to_solution(), None);
+}
+
+#[test]
+fn place_tile_keeps_rule_breaking_tiles() {
+    let mut tango = board(&["RR..", "....", "....", "...."]);
+    let result = tango.place_tile(2, 0, TangoTile::Red);
+    assert!(result.applied);
+    assert!(!result.valid);
+    assert!(!result.violations.is_empty());
+    assert_eq!(tango.get_tile((2, 0)), Some(TangoTile::Red));
+}
+
+#[test]
+fn checked_moves_revert_rule_breaking_tiles() {
+    let mut tango = board(&["RR..", "....", "....", "...."]);
+    let replay = tango.apply_moves([(Coord::new(2, 0), TangoTile::Red)]);
+    assert_eq!(replay.illegal_move, Some(0));
+    assert_eq!(tango.get_tile((2, 0)), Some(TangoTile::Empty));
+    assert!(tango.is_valid());
+}
@@ -0,0 +1,86 @@
+//! The Queens puzzle on hand-made boards.
+
+use ligames::{Coord, Game, Queens, QueensError, QueensGenerator};
+use serde_json::json;
+
+/// A board from rows of region numbers.
+fn regions(rows: [[usize; 4]; 4]) -> serde_json::Value {
+    json!({
+        "regions": { "width": 4, "height": 4, "tiles": rows.concat() },
+        "queens": { "width": 4, "height": 4, "tiles": vec![false; 16] },
+    })
+}
+
+fn queens(rows: [[usize; 4]; 4]) -> Queens {
+    serde_json::from_value(regions(rows)).unwrap()
+}
+
+/// Only one of the two non-touching placements on 4x4 keeps its queens in
+/// different regions.
+const UNIQUE: [[usize; 4]; 4] =
+    [[0, 0, 0, 0], [0, 1, 1, 1], [2, 2, 2, 2], [3, 3, 3, 3]];
+
+#[test]
+fn solves_a_unique_board() {
+    let board = queens(UNIQUE);
+    assert_eq!(board.count_solutions(usize::MAX), 1);
+    let solved = board.solution().unwrap();
+    assert!(solved.is_solved());
+    let placed: Vec<Coord> = solved
+        .queens
+        .iter_coords()
+        .filter(|(_, queen)| **queen)
+        .map(|(coord, _)| coord)
+        .collect();
+    let expected = [(1, 0), (3, 1), (0, 2), (2, 3)].map(Coord::from);
+    assert_eq!(placed, expected);
+}
+
+#[test]
+fn rejects_an_unsolvable_board() {
+    let board =
+        queens([[0, 0, 0, 0], [0, 1, 1, 0], [2, 2, 2, 2], [3, 3, 3, 3]]);
+    assert_eq!(board.count_solutions(usize::MAX), 0);
+    assert_eq!(board.solution(), None);
+}
+
+#[test]
+fn touching_queens_are_invalid() {
+    let mut board = queens(UNIQUE);
+    board.set_queen((1, 0), true);
+    board.set_queen((2, 1), true);
+    assert!(!board.is_valid());
+}
+
+#[test]
+fn deserializing_checks_the_board() {
+    let parse = |value| serde_json::from_value::<Queens>(value).map(|_| ());
+    let mut short = regions(UNIQUE);
+    short["regions"]["tiles"].as_array_mut().unwrap().pop();
+    assert!(parse(short).is_err());
+    let mut out_of_range = regions(UNIQUE);
+    out_of_range["regions"]["tiles"][0] = json!(4);
+    assert!(parse(out_of_range).is_err());
+    let mut mismatched = regions(UNIQUE);
+    mismatched["queens"]["width"] = json!(2);
+    assert!(parse(mismatched).is_err());
+}
+
+#[test]
+fn generated_boards_are_unique() {
+    let board = QueensGenerator::new(6).generate_unique().unwrap();
+    assert_eq!(board.count_solutions(2), 1);
+    assert!(board.solve().unwrap().is_solved());
+    assert_eq!(
+        QueensGenerator::new(2).generate_unique(),
+        Err(QueensError::Size(2))
+    );
+}
+
+#[test]
+fn generation_gives_up_after_its_attempts() {
+    let generator = QueensGenerator::new(6).with_max_attempts(0);
+    assert_eq!(generator.generate_unique(), Err(QueensError::Attempts(0)));
+    assert!(Queens::gave_up(&QueensError::Attempts(0)));
+    assert!(!Queens::gave_up(&QueensError::Size(2)));
+}
@@ -0,0 +1,125 @@
+//! Hand-crafted puzzles with known solution counts, checked against both the
+//! solver and a brute-force enumeration of every filling.
+
+use ligames::{SolveOutcome, Tango, TangoRestriction, TangoTile};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Boards are written as rows of `R`, `B` and `.` for empty cells.
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    rows: Vec<String>,
+    restrictions: Vec<TangoRestriction>,
+    solutions: usize,
+    solution: Option<Vec<String>>,
+    #[serde(default)]
+    violations: usize,
+}
+
+fn tiles(rows: &[String]) -> Vec<TangoTile> {
+    rows.iter()
+        .flat_map(|row| row.chars())
+        .map(|c| match c {
+            'R' => TangoTile::Red,
+            'B' => TangoTile::Blue,
+            _ => TangoTile::Empty,
+        })
+        .collect()
+}
+
+fn board(case: &Case) -> Tango {
+    serde_json::from_value(json!({
+        "grid": {
+            "width": case.rows[0].len(),
+            "height": case.rows.len(),
+            "tiles": tiles(&case.rows),
+        },
+        "restrictions": case.restrictions,
+    }))
+    .unwrap()
+}
+
+fn cases() -> Vec<Case> {
+    serde_json::from_str(include_str!("../fixtures/regression.json")).unwrap()
+}
+
+/// Counts the solutions by trying both colors in every empty cell.
+fn brute_force_count(tango: &Tango) -> usize {
+    let empty: Vec<_> = tango
+        .grid
+        .iter_coords()
+        .filter(|(_, tile)| **tile == TangoTile::Empty)
+        .map(|(coord, _)| coord)
+        .collect();
+    (0..1u32 << empty.len())
+        .filter(|mask| {
+            let mut board = tango.clone();
+            for (i, coord) in empty.iter().enumerate() {
+                *board.grid.get_mut(coord.x, coord.y).unwrap() =
+                    if mask >> i & 1 == 1 {
+                        TangoTile::Blue
+                    } else {
+                        TangoTile::Red
+                    };
+            }
+            board.is_solved()
+        })
+        .count()
+}
+
+#[test]
+fn solution_counts() {
+    for case in cases() {
+        let tango = board(&case);
+        assert_eq!(
+            tango.count_solutions(usize::MAX),
+            case.solutions,
+            "{}",
+            case.name
+        );
+        assert_eq!(brute_force_count(&tango), case.solutions, "{}", case.name);
+    }
+}
+
+#[test]
+fn solve_outcomes() {
+    for case in cases() {
+        let mut tango = board(&case);
+        match (tango.try_solve(), case.solutions) {
+            (SolveOutcome::Unique(solved), 1) => {
+                assert!(solved.is_solved(), "{}", case.name);
+                let expected = case.solution.as_deref().unwrap();
+                assert_eq!(solved.grid.tiles, tiles(expected), "{}", case.name);
+            }
+            (SolveOutcome::Multiple { count_capped }, n) if n > 1 => {
+                assert_eq!(count_capped, 2, "{}", case.name)
+            }
+            (SolveOutcome::None, 0) => {}
+            (outcome, _) => panic!("{}: unexpected {:?}", case.name, outcome),
+        }
+        assert_eq!(tango, board(&case), "{}: board changed", case.name);
+    }
+}
+
+#[test]
+fn violations() {
+    for case in cases() {
+        let tango = board(&case);
+        assert_eq!(tango.violations().len(), case.violations, "{}", case.name);
+        assert_eq!(tango.is_valid(), case.violations == 0, "{}", case.name);
+    }
+}
+
+#[test]
+fn linkedin_fixture_is_unique() {
+    let mut tango = Tango::from_linkedin_json(include_str!(
+        "../fixtures/linkedin_tango.json"
+    ))
+    .unwrap();
+    let SolveOutcome::Unique(solved) = tango.try_solve() else {
+        panic!("LinkedIn fixture should have exactly one solution");
+    };
+    assert!(solved.is_solved());
+    assert!(solved.violations().is_empty());
+}
@@ -0,0 +1,174 @@
+//! Relations between cells established by restrictions.
+
+mod common;
+
+use common::{board, different, restricted, same};
+use ligames::{Coord, RestrictionSet, TangoError};
+
+const EMPTY: [&str; 4] = ["....", "....", "....", "...."];
+
+#[test]
+fn chain_groups_cells_with_parities() {
+    // A = B × C along the top row
+    let tango =
+        restricted(&EMPTY, &[same((0, 0), (1, 0)), different((1, 0), (2, 0))]);
+    assert_eq!(
+        tango.restriction_components(),
+        [vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]]
+    );
+    assert_eq!(tango.must_match((0, 0), (1, 0)), Some(true));
+    assert_eq!(tango.must_match((1, 0), (2, 0)), Some(false));
+    assert_eq!(tango.must_match((0, 0), (2, 0)), Some(false));
+    assert_eq!(tango.must_match((0, 0), (3, 3)), None);
+    assert!(tango.restrictions_satisfiable());
+}
+
+#[test]
+fn contradicting_chain_is_unsatisfiable() {
+    // Around a 2x2 square, one `Different` can't be balanced
+    let tango = restricted(
+        &EMPTY,
+        &[
+            same((0, 0), (1, 0)),
+            different((1, 0), (1, 1)),
+            same((1, 1), (0, 1)),
+            same((0, 1), (0, 0)),
+        ],
+    );
+    assert!(!tango.restrictions_satisfiable());
+    assert_eq!(tango.restriction_components().len(), 1);
+}
+
+#[test]
+fn odd_cycle_of_differents_is_unsatisfiable() {
+    // Three `Different`s around a 2x2 square would need a third color
+    let tango = restricted(
+        &EMPTY,
+        &[
+            different((0, 0), (1, 0)),
+            different((1, 0), (1, 1)),
+            different((1, 1), (0, 1)),
+            same((0, 1), (0, 0)),
+        ],
+    );
+    assert!(!tango.restrictions_satisfiable());
+    assert_eq!(tango.count_solutions(1), 0);
+}
+
+#[test]
+fn unrestricted_board_has_no_components() {
+    let tango = board(&EMPTY);
+    assert!(tango.restriction_components().is_empty());
+    assert!(tango.restrictions_satisfiable());
+}
+
+#[test]
+fn edges_cover_every_neighboring_pair_once() {
+    for (width, height) in [(2, 2), (4, 2), (2, 6), (6, 8)] {
+        let rows = vec![".".repeat(width); height];
+        let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+        let edges: Vec<_> = board(&rows).edges().collect();
+        assert_eq!(edges.len(), width * (height - 1) + height * (width - 1));
+        for &(a, b) in &edges {
+            assert_eq!(a.x.abs_diff(b.x) + a.y.abs_diff(b.y), 1);
+            assert!(!edges.contains(&(b, a)));
+        }
+    }
+}
+
+#[test]
+fn restriction_sets_drop_duplicates() {
+    let set = RestrictionSet::try_from_iter([
+        different((1, 1), (1, 0)),
+        same((0, 0), (1, 0)),
+        same((1, 0), (0, 0)),
+        different((1, 0), (1, 1)),
+        same((0, 0), (1, 0)),
+    ])
+    .unwrap();
+    assert_eq!(
+        set.as_slice(),
+        [same((0, 0), (1, 0)), different((1, 0), (1, 1))]
+    );
+}
+
+#[test]
+fn restriction_sets_reject_contradictions() {
+    let set = RestrictionSet::try_from_iter([
+        same((0, 0), (1, 0)),
+        different((1, 0), (0, 0)),
+    ]);
+    assert_eq!(
+        set,
+        Err(TangoError::ContradictingRestriction(
+            Coord::new(0, 0),
+            Coord::new(1, 0)
+        ))
+    );
+}
+
+#[test]
+fn restrictions_for_cell_lists_incident_restrictions() {
+    let tango = restricted(
+        &EMPTY,
+        &[
+            same((0, 0), (1, 0)),
+            different((2, 2), (2, 3)),
+            different((1, 1), (1, 0)),
+        ],
+    );
+    assert_eq!(
+        tango.restrictions_for_cell(1, 0),
+        [&same((0, 0), (1, 0)), &different((1, 1), (1, 0))]
+    );
+    assert!(tango.restrictions_for_cell(3, 0).is_empty());
+    assert!(tango.restrictions_for_cell(9, 9).is_empty());
+}
+
+#[test]
+fn can_add_restriction_names_the_reason_for_a_rejection() {
+    // The chain forces (0, 2) and (0, 3) apart
+    let tango = restricted(
+        &["RB..", "....", "....", "...."],
+        &[
+            same((0, 2), (1, 2)),
+            different((1, 2), (1, 3)),
+            same((1, 3), (0, 3)),
+        ],
+    );
+    let c = Coord::new;
+    let check = |restriction| tango.can_add_restriction(&restriction);
+    assert_eq!(
+        check(same((3, 3), (4, 3))),
+        Err(TangoError::OutOfBounds(c(4, 3)))
+    );
+    assert_eq!(
+        check(same((0, 0), (2, 0))),
+        Err(TangoError::NotAdjacent(c(0, 0), c(2, 0)))
+    );
+    assert_eq!(
+        check(same((0, 0), (1, 1))),
+        Err(TangoError::NotAdjacent(c(0, 0), c(1, 1)))
+    );
+    assert_eq!(
+        check(same((1, 2), (0, 2))),
+        Err(TangoError::DuplicateRestriction(c(1, 2), c(0, 2)))
+    );
+    assert_eq!(
+        check(different((0, 2), (1, 2))),
+        Err(TangoError::ContradictingRestriction(c(0, 2), c(1, 2)))
+    );
+    assert_eq!(
+        check(same((0, 0), (1, 0))),
+        Err(TangoError::BrokenRestriction(c(0, 0), c(1, 0)))
+    );
+    assert_eq!(
+        check(same((0, 2), (0, 3))),
+        Err(TangoError::ContradictingCycle(c(0, 2), c(0, 3)))
+    );
+
+    assert_eq!(check(different((0, 0), (1, 0))), Ok(()));
+    assert_eq!(check(different((0, 2), (0, 3))), Ok(()));
+    assert_eq!(check(same((2, 0), (3, 0))), Ok(()));
+    assert_eq!(tango.restrictions().len(), 3);
+}
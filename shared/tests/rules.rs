@@ -0,0 +1,54 @@
+//! Rule variants and line rules.
+
+mod common;
+
+use common::board;
+use ligames::{TangoRules, TangoTile, ViolationKind};
+
+/// Valid along every row and column, but with a red main diagonal.
+const CHECKERED: [&str; 4] = ["RBRB", "BRBR", "RBRB", "BRBR"];
+
+#[test]
+fn diagonal_triples_break_the_diagonal_rule() {
+    let mut tango = board(&CHECKERED);
+    assert!(tango.is_solved());
+    tango.rules.check_diagonals = true;
+    assert!(!tango.is_valid());
+    assert!(!tango.is_solved());
+    assert!(tango.violations().iter().any(|v| v.kind
+        == ViolationKind::DiagonalRun {
+            tile: TangoTile::Red
+        }));
+}
+
+#[test]
+fn diagonal_rule_prunes_solutions() {
+    let mut tango = board(&["R...", "....", "....", "...."]);
+    let without = tango.count_solutions(usize::MAX);
+    tango.rules.check_diagonals = true;
+    let with = tango.count_solutions(usize::MAX);
+    assert!(with < without);
+    assert!(tango.solution().is_none_or(|solved| solved.is_solved()));
+}
+
+#[test]
+fn run_limits_apply_per_direction() {
+    let rules = TangoRules {
+        row_max_run: 2,
+        col_max_run: 3,
+        ..Default::default()
+    };
+    let mut vertical =
+        board(&["R.....", "R.....", "R.....", "......", "......", "......"]);
+    let mut horizontal =
+        board(&["RRR...", "......", "......", "......", "......", "......"]);
+    assert!(!vertical.is_valid());
+    vertical.rules = rules;
+    horizontal.rules = rules;
+    assert!(vertical.is_valid());
+    assert!(!horizontal.is_valid());
+    assert!(horizontal.violations().iter().any(|v| v.kind
+        == ViolationKind::Run {
+            tile: TangoTile::Red
+        }));
+}
@@ -0,0 +1,88 @@
+//! The DIMACS CNF export, checked with a small DPLL solver.
+
+mod common;
+
+use common::{board, different, restricted, same};
+use ligames::{Tango, TangoGenerator};
+
+/// Clauses of a DIMACS CNF.
+fn parse(dimacs: &str) -> Vec<Vec<i32>> {
+    dimacs
+        .lines()
+        .filter(|line| !line.starts_with('c') && !line.starts_with('p'))
+        .map(|line| {
+            line.split_whitespace()
+                .map(|literal| literal.parse().unwrap())
+                .take_while(|&literal| literal != 0)
+                .collect()
+        })
+        .collect()
+}
+
+/// Collects a model of the clauses as the list of true literals, with unit
+/// propagation and branching on the lowest open variable.
+fn dpll(clauses: &[Vec<i32>], model: &mut Vec<i32>) -> bool {
+    let open: Vec<Vec<i32>> = clauses
+        .iter()
+        .filter(|clause| !clause.iter().any(|l| model.contains(l)))
+        .map(|clause| {
+            let open = clause.iter().filter(|l| !model.contains(&-**l));
+            open.copied().collect()
+        })
+        .collect();
+    if open.iter().any(Vec::is_empty) {
+        return false;
+    }
+    let branch = match open.iter().find(|clause| clause.len() == 1) {
+        Some(unit) => vec![unit[0]],
+        None => match open.iter().flatten().map(|l| l.abs()).min() {
+            Some(variable) => vec![variable, -variable],
+            None => return true,
+        },
+    };
+    for literal in branch {
+        model.push(literal);
+        if dpll(clauses, model) {
+            return true;
+        }
+        model.pop();
+    }
+    false
+}
+
+fn sat_solution(tango: &Tango) -> Option<Tango> {
+    let mut model = Vec::new();
+    dpll(&parse(&tango.to_dimacs()), &mut model)
+        .then(|| tango.from_sat_assignment(&model))
+}
+
+#[test]
+fn cnf_is_satisfiable_iff_the_board_is_solvable() {
+    let generated = TangoGenerator::new(4, 4).unwrap().with_seed(5);
+    let boards = [
+        board(&["R.", ".."]),
+        board(&["....", "....", "....", "...."]),
+        generated.generate_unique(),
+        generated.generate(),
+        // Breaks no rule yet, but the restriction can't hold
+        restricted(&["R.", ".."], &[same((0, 0), (1, 0))]),
+        restricted(
+            &["....", "....", "....", "...."],
+            &[
+                different((0, 0), (1, 0)),
+                different((1, 0), (1, 1)),
+                different((1, 1), (0, 1)),
+                same((0, 1), (0, 0)),
+            ],
+        ),
+        board(&["RR..", "....", "....", "...."]),
+    ];
+    for tango in boards {
+        let solvable = tango.count_solutions(1) > 0;
+        let solution = sat_solution(&tango);
+        assert_eq!(solution.is_some(), solvable, "{}", tango);
+        if let Some(solution) = solution {
+            assert!(solution.is_solved(), "{}", solution);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Wire formats of boards and their parts.
+
+mod common;
+
+use common::{board, different, restricted, same, tiles};
+use ligames::{
+    BoardKind, CodeError, Coord, RestrictionsByKind, Tango, TangoGenerator,
+    TangoRestriction, TangoRules, TangoTile,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct NullableGrid {
+    width: usize,
+    height: usize,
+    #[serde(with = "ligames::nullable_tile::vec")]
+    tiles: Vec<TangoTile>,
+    #[serde(with = "ligames::nullable_tile")]
+    selected: TangoTile,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct NamedCursor {
+    #[serde(with = "ligames::named_coord")]
+    cursor: Coord,
+    #[serde(with = "ligames::named_coord::restrictions")]
+    restrictions: Vec<TangoRestriction>,
+}
+
+#[test]
+fn nullable_tiles_round_trip() {
+    let grid = NullableGrid {
+        width: 2,
+        height: 2,
+        tiles: tiles(&["R.", ".B"]),
+        selected: TangoTile::Empty,
+    };
+    let value = serde_json::to_value(&grid).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "width": 2,
+            "height": 2,
+            "tiles": ["Red", null, null, "Blue"],
+            "selected": null,
+        })
+    );
+    assert_eq!(serde_json::from_value::<NullableGrid>(value).unwrap(), grid);
+}
+
+#[test]
+fn restrictions_by_kind_partition_the_restrictions() {
+    let restrictions = [
+        same((0, 0), (1, 0)),
+        different((1, 0), (2, 0)),
+        same((0, 1), (0, 2)),
+        different((3, 3), (3, 2)),
+    ];
+    let tango = restricted(&["....", "....", "....", "...."], &restrictions);
+    let by_kind = tango.restrictions_by_kind();
+    let pair =
+        |a: (usize, usize), b: (usize, usize)| (Coord::from(a), Coord::from(b));
+    assert_eq!(by_kind.same, [pair((0, 0), (1, 0)), pair((0, 1), (0, 2))]);
+    assert_eq!(
+        by_kind.different,
+        [pair((1, 0), (2, 0)), pair((3, 3), (3, 2))]
+    );
+
+    let value = serde_json::to_value(&by_kind).unwrap();
+    assert_eq!(value["same"].as_array().unwrap().len(), 2);
+    assert_eq!(value["different"].as_array().unwrap().len(), 2);
+    let mut round_trip: Vec<TangoRestriction> =
+        serde_json::from_value::<RestrictionsByKind>(value)
+            .unwrap()
+            .into();
+    round_trip.sort();
+    let mut expected = restrictions.to_vec();
+    expected.sort();
+    assert_eq!(round_trip, expected);
+}
+
+#[test]
+fn boards_serialize_their_kind() {
+    let puzzle = TangoGenerator::new(4, 4)
+        .unwrap()
+        .with_seed(0)
+        .generate_unique();
+    assert_eq!(puzzle.kind, BoardKind::Puzzle);
+    assert_eq!(serde_json::to_value(&puzzle).unwrap()["kind"], "Puzzle");
+
+    let solution = puzzle.solution().unwrap();
+    assert_eq!(serde_json::to_value(&solution).unwrap()["kind"], "Solution");
+    let random = Tango::random_solution(4, 4, &mut StdRng::seed_from_u64(0));
+    assert_eq!(random.unwrap().kind, BoardKind::Solution);
+
+    // Playing a move leaves the puzzle state
+    let mut played = puzzle.clone();
+    let (coord, tile) = played
+        .grid
+        .iter_coords()
+        .find(|(_, t)| **t == TangoTile::Empty)
+        .map(|(c, _)| (c, solution.get_tile(c).unwrap()))
+        .unwrap();
+    played.place_tile(coord.x, coord.y, tile);
+    assert_eq!(serde_json::to_value(&played).unwrap()["kind"], "InProgress");
+}
+
+#[test]
+fn minimal_json_gets_defaults() {
+    let tango: Tango = serde_json::from_value(json!({
+        "grid": { "width": 2, "height": 2, "tiles": tiles(&["R.", ".."]) },
+    }))
+    .unwrap();
+    assert!(tango.restrictions().is_empty());
+    assert_eq!(tango.rules, TangoRules::default());
+    assert_eq!(tango.kind, BoardKind::InProgress);
+    assert_eq!(tango.count_solutions(2), 1);
+
+    // Missing rule fields fall back one by one
+    let tango: Tango = serde_json::from_value(json!({
+        "grid": { "width": 2, "height": 2, "tiles": tiles(&["..", ".."]) },
+        "restrictions": [],
+        "rules": { "check_diagonals": true },
+    }))
+    .unwrap();
+    assert!(tango.rules.check_diagonals);
+    assert_eq!(tango.rules.row_max_run, 2);
+    assert_eq!(tango.rules.col_max_run, 2);
+}
+
+#[test]
+fn named_coords_serialize_as_objects() {
+    let cursor = NamedCursor {
+        cursor: Coord { x: 3, y: 1 },
+        restrictions: vec![same((0, 0), (1, 0)), different((2, 1), (2, 2))],
+    };
+    let value = serde_json::to_value(&cursor).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "cursor": { "x": 3, "y": 1 },
+            "restrictions": [
+                { "Same": [{ "x": 0, "y": 0 }, { "x": 1, "y": 0 }] },
+                { "Different": [{ "x": 2, "y": 1 }, { "x": 2, "y": 2 }] },
+            ],
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<NamedCursor>(value).unwrap(),
+        cursor
+    );
+}
+
+#[test]
+fn boards_without_restrictions_leave_the_key_out() {
+    let tango = board(&["R.", ".B"]);
+    let value = serde_json::to_value(&tango).unwrap();
+    assert!(value.get("restrictions").is_none());
+    assert_eq!(serde_json::from_value::<Tango>(value).unwrap(), tango);
+
+    let tango = restricted(&["R.", ".."], &[same((0, 0), (1, 0))]);
+    let value = serde_json::to_value(&tango).unwrap();
+    assert_eq!(value["restrictions"], json!([{ "Same": [[0, 0], [1, 0]] }]));
+    assert_eq!(serde_json::from_value::<Tango>(value).unwrap(), tango);
+}
+
+#[test]
+fn solution_bits_round_trip() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for (width, height) in [(2, 2), (4, 6), (6, 6), (10, 4), (12, 12)] {
+        for _ in 0..5 {
+            let solution =
+                Tango::random_solution(width, height, &mut rng).unwrap();
+            let bits = solution.solution_bits().unwrap();
+            assert_eq!(bits.len(), 2 + (width * height).div_ceil(8));
+            let read = Tango::from_solution_bits(&bits).unwrap();
+            assert_eq!(read.grid, solution.grid);
+            assert_eq!(read.kind, BoardKind::Solution);
+        }
+    }
+    assert_eq!(board(&["R.", ".."]).solution_bits(), None);
+    assert_eq!(
+        Tango::from_solution_bits(&[2, 2]),
+        Err(CodeError::ByteCount(2))
+    );
+}
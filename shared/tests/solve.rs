@@ -0,0 +1,119 @@
+//! Outcomes of the solver entry points, see `Tango::try_solve`.
+
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{board, different, restricted, same, tiles};
+use ligames::{SolveOutcome, TangoGenerator};
+
+#[test]
+fn unique_outcome() {
+    let mut tango = board(&["R.", ".."]);
+    let SolveOutcome::Unique(solved) = tango.try_solve() else {
+        panic!("expected a unique solution");
+    };
+    assert!(solved.is_solved());
+    assert_eq!(solved.grid.tiles, tiles(&["RB", "BR"]));
+}
+
+#[test]
+fn multiple_outcome() {
+    let mut tango = board(&["..", ".."]);
+    assert_eq!(
+        tango.try_solve(),
+        SolveOutcome::Multiple { count_capped: 2 }
+    );
+}
+
+#[test]
+fn no_solution_outcome() {
+    let mut tango = restricted(&["R.", ".."], &[same((0, 0), (1, 0))]);
+    assert!(tango.is_valid());
+    assert_eq!(tango.try_solve(), SolveOutcome::None);
+}
+
+#[test]
+fn broken_full_board_has_no_solution() {
+    let rows = ["RRRR", "BBBB", "RRRR", "BBBB"];
+    let cancel = AtomicBool::new(false);
+    assert_eq!(board(&rows).try_solve(), SolveOutcome::None);
+    assert_eq!(board(&rows).solve_cancellable(&cancel), SolveOutcome::None);
+    assert_eq!(board(&rows).solve_with_limit(1000), SolveOutcome::None);
+    assert_eq!(board(&rows).solution(), None);
+}
+
+#[test]
+fn tiny_step_limit_is_limited() {
+    let mut tango = board(&["......"; 6]);
+    assert_eq!(tango.solve_with_limit(5), SolveOutcome::Limited);
+    assert!(matches!(
+        tango.solve_with_limit(1_000_000),
+        SolveOutcome::Multiple { .. }
+    ));
+}
+
+#[test]
+fn solving_restores_the_borrowed_board() {
+    let mut tango = TangoGenerator::new(6, 6)
+        .unwrap()
+        .with_seed(7)
+        .generate_unique();
+    let before = serde_json::to_vec(&tango).unwrap();
+    let cancel = AtomicBool::new(true);
+
+    assert!(matches!(tango.try_solve(), SolveOutcome::Unique(_)));
+    assert_eq!(serde_json::to_vec(&tango).unwrap(), before);
+    // Searches aborted halfway take back their placements as well
+    assert_eq!(tango.solve_with_limit(10), SolveOutcome::Limited);
+    assert_eq!(serde_json::to_vec(&tango).unwrap(), before);
+    assert_eq!(tango.solve_cancellable(&cancel), SolveOutcome::Cancelled);
+    assert_eq!(serde_json::to_vec(&tango).unwrap(), before);
+}
+
+#[test]
+fn cancelling_stops_a_running_search() {
+    // An odd cycle of `Different`s in the last cell the search reaches
+    // leaves it exploring every other cell first
+    let mut tango = restricted(
+        &["............"; 12],
+        &[
+            different((10, 10), (11, 10)),
+            different((11, 10), (11, 11)),
+            different((11, 11), (10, 11)),
+            same((10, 11), (10, 10)),
+        ],
+    );
+    let cancel = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let search = scope.spawn(|| tango.solve_cancellable(&cancel));
+        thread::sleep(Duration::from_millis(100));
+        cancel.store(true, Ordering::Relaxed);
+        let cancelled_at = Instant::now();
+        assert_eq!(search.join().unwrap(), SolveOutcome::Cancelled);
+        assert!(cancelled_at.elapsed() < Duration::from_secs(1));
+    });
+}
+
+#[test]
+fn logic_only_puzzles_need_no_guesses() {
+    for seed in 0..5 {
+        let puzzle = TangoGenerator::new(6, 6)
+            .unwrap()
+            .with_seed(seed)
+            .generate_logic_only();
+        let metrics = puzzle.solve_metrics();
+        assert_eq!(metrics.guesses, 0, "seed {seed}");
+        assert_eq!(metrics.backtracks, 0, "seed {seed}");
+        assert!(metrics.steps > 0);
+    }
+    // Every cell of an empty board fits both colors
+    assert!(
+        board(&["....", "....", "....", "...."])
+            .solve_metrics()
+            .guesses
+            > 0
+    );
+}
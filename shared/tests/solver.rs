@@ -0,0 +1,106 @@
+//! The pluggable solvers behind `TangoSolver`.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::board;
+use ligames::{
+    BranchOrder, RecursiveTangoSolver, Tango, TangoGenerator, TangoSolver,
+};
+
+#[test]
+fn default_solver_implements_the_trait() {
+    let solver: Arc<dyn TangoSolver + Send + Sync> =
+        Arc::new(RecursiveTangoSolver::default());
+    let unique = board(&["R.", ".."]);
+    assert_eq!(solver.count_capped(&unique, 3), 1);
+    assert_eq!(solver.solution(&unique), unique.solution());
+    assert!(solver.solution(&unique).unwrap().is_solved());
+    assert_eq!(solver.count_capped(&board(&["..", ".."]), 3), 2);
+}
+
+/// Counts how often the generator asks for solution counts.
+#[derive(Default)]
+struct CountingSolver {
+    calls: AtomicUsize,
+}
+
+impl TangoSolver for CountingSolver {
+    fn count_capped(&self, tango: &Tango, cap: usize) -> usize {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        RecursiveTangoSolver::default().count_capped(tango, cap)
+    }
+
+    fn solution(&self, tango: &Tango) -> Option<Tango> {
+        RecursiveTangoSolver::default().solution(tango)
+    }
+}
+
+#[test]
+fn generator_uses_the_plugged_in_solver() {
+    let solver = Arc::new(CountingSolver::default());
+    let tango = TangoGenerator::new(4, 4)
+        .unwrap()
+        .with_solver(solver.clone())
+        .generate_unique();
+    assert!(solver.calls.load(Ordering::Relaxed) > 0);
+    assert_eq!(tango.count_solutions(2), 1);
+}
+
+#[test]
+fn propagation_keeps_the_solution_counts() {
+    let brute_force = RecursiveTangoSolver {
+        propagation_threshold: usize::MAX,
+        ..Default::default()
+    };
+    let propagating = RecursiveTangoSolver {
+        propagation_threshold: 0,
+        ..Default::default()
+    };
+    // Sizes on both sides of the default threshold of 64 cells
+    for (width, height) in [(6, 6), (6, 10), (8, 8)] {
+        for seed in 0..5 {
+            let tango = TangoGenerator::new(width, height)
+                .unwrap()
+                .with_seed(seed)
+                .generate();
+            assert_eq!(
+                brute_force.count_capped(&tango, 5),
+                propagating.count_capped(&tango, 5),
+                "{}",
+                tango
+            );
+        }
+    }
+}
+
+#[test]
+fn restriction_first_takes_fewer_steps_on_restricted_boards() {
+    let solver = |branch_order| RecursiveTangoSolver {
+        branch_order,
+        ..Default::default()
+    };
+    let (row_major, restriction_first) = (
+        solver(BranchOrder::RowMajor),
+        solver(BranchOrder::RestrictionFirst),
+    );
+    for seed in 0..4 {
+        // Only restrictions and no givens, so the order matters most
+        let puzzle = TangoGenerator::new(6, 6)
+            .unwrap()
+            .with_seed(seed)
+            .generate_restriction_only(1000)
+            .unwrap();
+        assert!(
+            restriction_first.count_steps(&puzzle)
+                < row_major.count_steps(&puzzle),
+            "seed {seed}"
+        );
+        assert_eq!(
+            restriction_first.count_capped(&puzzle, 3),
+            row_major.count_capped(&puzzle, 3)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Stepping through the search one tile at a time.
+
+mod common;
+
+use common::{different, restricted, same};
+use ligames::{SteppingSolver, Tango};
+
+/// A unique puzzle whose first guess of red is wrong.
+fn puzzle() -> Tango {
+    restricted(
+        &["R.....", ".RR...", "....B.", "......", "...B..", "......"],
+        &[
+            same((0, 5), (1, 5)),
+            same((2, 2), (2, 3)),
+            different((4, 3), (4, 4)),
+            different((5, 2), (5, 3)),
+            different((2, 0), (3, 0)),
+            different((3, 3), (4, 3)),
+            same((3, 0), (4, 0)),
+        ],
+    )
+}
+
+#[test]
+fn restores_the_snapshot_taken_before_a_guess() {
+    let tango = puzzle();
+    let mut solver = SteppingSolver::new(&tango);
+    let (snapshot, before) = loop {
+        let snapshot = solver.snapshot();
+        let before = solver.board().clone();
+        solver.step().expect("A guess comes before the dead end");
+        if solver.guesses().count() == 1 {
+            break (snapshot, before);
+        }
+    };
+    // Forced steps after the guess until it runs into a dead end
+    while solver.step().is_some() {}
+    assert!(!solver.board().is_solved());
+
+    solver.restore(&snapshot);
+    assert_eq!(solver.board(), &before);
+    assert_eq!(solver.snapshot(), snapshot);
+    assert_eq!(solver.guesses().count(), 0);
+}
+
+#[test]
+fn backtracking_past_the_wrong_guess_solves_the_puzzle() {
+    let tango = puzzle();
+    let mut solver = SteppingSolver::new(&tango);
+    loop {
+        if solver.step().is_some() {
+            continue;
+        }
+        if solver.board().is_solved() {
+            break;
+        }
+        solver.backtrack().expect("The puzzle has a solution");
+    }
+    assert_eq!(solver.board().grid, tango.solution().unwrap().grid);
+}
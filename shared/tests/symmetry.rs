@@ -0,0 +1,117 @@
+//! Mirror images, rotations and color swaps of boards.
+
+mod common;
+
+use common::{board, different, restricted, same};
+use ligames::{Coord, Tango, TangoError};
+
+const EMPTY: [&str; 4] = ["....", "....", "....", "...."];
+
+#[test]
+fn symmetric_solutions_are_counted_once() {
+    let tango = board(&EMPTY);
+    let raw = tango.count_solutions(usize::MAX);
+    let distinct = tango.distinct_solutions();
+    assert!(distinct.len() < raw, "{} of {}", distinct.len(), raw);
+
+    let canonical: Vec<_> = distinct
+        .iter()
+        .map(|solution| solution.canonical())
+        .collect();
+    for (i, a) in canonical.iter().enumerate() {
+        assert!(distinct[i].is_solved());
+        assert!(canonical[i + 1..].iter().all(|b| a != b));
+    }
+}
+
+#[test]
+fn canonical_form_ignores_symmetry() {
+    let tango = board(&["R...", "....", "....", "...."]);
+    let mirrored = board(&["...R", "....", "....", "...."]);
+    let swapped = board(&["....", "....", "....", "...B"]);
+    assert_eq!(tango.canonical(), mirrored.canonical());
+    assert_eq!(tango.canonical(), swapped.canonical());
+    assert_ne!(tango.canonical(), board(&EMPTY).canonical());
+}
+
+#[test]
+fn swapping_lines_moves_their_restrictions() {
+    let original = restricted(
+        &["R...", "..B.", "....", "...B"],
+        &[same((0, 0), (1, 0)), different((2, 1), (2, 2))],
+    );
+    let mut tango = original.clone();
+    tango.swap_rows(0, 3).unwrap();
+    assert_eq!(tango.grid, board(&["...B", "..B.", "....", "R..."]).grid);
+    assert_eq!(
+        tango.restrictions(),
+        [same((0, 3), (1, 3)), different((2, 1), (2, 2))]
+    );
+    tango.swap_rows(1, 2).unwrap();
+    assert_eq!(
+        tango.restrictions(),
+        [same((0, 3), (1, 3)), different((2, 2), (2, 1))]
+    );
+    tango.swap_rows(2, 1).unwrap();
+    tango.swap_rows(3, 0).unwrap();
+    assert_eq!(tango, original);
+
+    tango.swap_columns(2, 3).unwrap();
+    assert_eq!(tango.grid, board(&["R...", "...B", "....", "..B."]).grid);
+    assert_eq!(
+        tango.restrictions(),
+        [same((0, 0), (1, 0)), different((3, 1), (3, 2))]
+    );
+    tango.swap_columns(3, 2).unwrap();
+    assert_eq!(tango, original);
+}
+
+#[test]
+fn swaps_that_would_split_a_restriction_fail() {
+    let original =
+        restricted(&["R...", "....", "....", "...."], &[same((0, 0), (1, 0))]);
+    let mut tango = original.clone();
+    assert_eq!(
+        tango.swap_columns(1, 3),
+        Err(TangoError::NotAdjacent(Coord::new(0, 0), Coord::new(3, 0)))
+    );
+    assert_eq!(
+        tango.swap_rows(0, 4),
+        Err(TangoError::OutOfBounds(Coord::new(0, 4)))
+    );
+    assert_eq!(tango, original);
+}
+
+/// Restrictions that map onto each other when mirrored left to right.
+fn mirrored_pairs() -> Tango {
+    restricted(&EMPTY, &[same((0, 0), (1, 0)), same((2, 0), (3, 0))])
+}
+
+#[test]
+fn canonical_form_of_restricted_boards_ignores_their_listing() {
+    let tango = mirrored_pairs();
+    let relisted =
+        restricted(&EMPTY, &[same((3, 0), (2, 0)), same((1, 0), (0, 0))]);
+    let canonical = tango.canonical();
+    assert_eq!(relisted.canonical(), canonical);
+    assert_eq!(
+        format!("{:?}", relisted.canonical()),
+        format!("{:?}", canonical)
+    );
+
+    let restrictions = canonical.restrictions();
+    assert!(restrictions.is_sorted());
+    assert!(restrictions.iter().all(|r| {
+        let (a, b) = r.cells();
+        a < b
+    }));
+}
+
+#[test]
+fn symmetric_solutions_of_restricted_boards_are_counted_once() {
+    let tango = mirrored_pairs();
+    let raw = tango.count_solutions(usize::MAX);
+    let distinct = tango.distinct_solutions().len();
+    // Color swaps alone would merge pairs, the mirror merges more
+    assert!(distinct < raw / 2, "{} of {}", distinct, raw);
+}
@@ -0,0 +1,56 @@
+//! Splitting difficulty scores into tiers.
+
+use ligames::{DifficultyTier, TangoGenerator, TierThresholds};
+
+/// How many of the scores fall into each tier, easiest first.
+fn tier_sizes(thresholds: &TierThresholds, scores: &[usize]) -> [usize; 3] {
+    let mut sizes = [0; 3];
+    for &score in scores {
+        sizes[match thresholds.tier(score) {
+            DifficultyTier::Easy => 0,
+            DifficultyTier::Medium => 1,
+            DifficultyTier::Hard => 2,
+        }] += 1;
+    }
+    sizes
+}
+
+#[test]
+fn distinct_scores_split_into_thirds() {
+    let scores: Vec<usize> = (1..=90).rev().collect();
+    let thresholds = TierThresholds::from_scores(scores.clone()).unwrap();
+    assert!(thresholds.medium <= thresholds.hard);
+    assert_eq!(tier_sizes(&thresholds, &scores), [30, 30, 30]);
+    assert_eq!(TierThresholds::from_scores(vec![]), None);
+}
+
+#[test]
+fn many_equal_scores_split_unevenly() {
+    let mut scores = vec![5; 10];
+    scores.push(9);
+    let thresholds = TierThresholds::from_scores(scores.clone()).unwrap();
+    // Both terciles are 5, and with them every score is hard
+    assert_eq!((thresholds.medium, thresholds.hard), (5, 5));
+    assert_eq!(tier_sizes(&thresholds, &scores), [0, 0, 11]);
+}
+
+#[test]
+fn calibrated_tiers_hold_about_a_third_each() {
+    let generator = TangoGenerator::new(4, 4).unwrap().with_seed(3);
+    let thresholds = generator.calibrate_tiers(30).unwrap();
+    assert!(thresholds.medium <= thresholds.hard);
+
+    // The same seed gives the same sample the tiers were calibrated on
+    let mut scores = generator.difficulty_sample(30);
+    for size in tier_sizes(&thresholds, &scores) {
+        assert!((5..=15).contains(&size), "{thresholds:?}");
+    }
+    // Harder scores never land in an easier tier
+    scores.sort_unstable();
+    let tiers: Vec<[usize; 3]> = scores
+        .iter()
+        .map(|&score| tier_sizes(&thresholds, &[score]))
+        .collect();
+    assert!(tiers.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert_eq!(generator.calibrate_tiers(0), None);
+}
@@ -0,0 +1,18 @@
+//! Single tiles.
+
+use ligames::TangoTile;
+
+#[test]
+fn opposite_of_red_is_blue() {
+    assert_eq!(TangoTile::Red.opposite(), Some(TangoTile::Blue));
+}
+
+#[test]
+fn opposite_of_blue_is_red() {
+    assert_eq!(TangoTile::Blue.opposite(), Some(TangoTile::Red));
+}
+
+#[test]
+fn empty_has_no_opposite() {
+    assert_eq!(TangoTile::Empty.opposite(), None);
+}
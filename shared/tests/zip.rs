@@ -0,0 +1,54 @@
+//! The Zip puzzle on hand-made boards.
+
+use ligames::{Coord, Game, Zip, ZipError, ZipGenerator};
+use serde_json::json;
+
+/// A 2x2 board with the numbers at the given cells, in order.
+fn zip(numbered: &[(usize, usize)]) -> Zip {
+    let mut tiles = vec![None; 4];
+    for (number, &(x, y)) in numbered.iter().enumerate() {
+        tiles[y * 2 + x] = Some(number + 1);
+    }
+    serde_json::from_value(json!({
+        "numbers": { "width": 2, "height": 2, "tiles": tiles },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn finds_the_only_path() {
+    let board = zip(&[(0, 0), (1, 0)]);
+    assert_eq!(board.count_solutions(usize::MAX), 1);
+    let solved = board.solution().unwrap();
+    assert!(solved.is_solved());
+    let expected = [(0, 0), (0, 1), (1, 1), (1, 0)].map(Coord::from);
+    assert_eq!(solved.path, expected);
+}
+
+#[test]
+fn rejects_an_unsolvable_board() {
+    // A path over all four cells ends on the other color of the chessboard
+    let board = zip(&[(0, 0), (1, 1)]);
+    assert_eq!(board.count_solutions(usize::MAX), 0);
+    assert_eq!(board.solution(), None);
+}
+
+#[test]
+fn paths_must_pass_numbers_in_order() {
+    let mut board = zip(&[(0, 0), (0, 1), (1, 0)]);
+    board.path = [(0, 0), (0, 1), (1, 1), (1, 0)].map(Coord::from).to_vec();
+    assert!(board.is_solved());
+    board.path.reverse();
+    assert!(!board.is_solved());
+}
+
+#[test]
+fn generated_boards_are_unique() {
+    let board = ZipGenerator::new(5).generate_unique().unwrap();
+    assert_eq!(board.count_solutions(2), 1);
+    assert!(board.solve().unwrap().is_solved());
+    assert_eq!(
+        ZipGenerator::new(1).generate_unique(),
+        Err(ZipError::Size(1))
+    );
+}